@@ -0,0 +1,418 @@
+use crate::applications::keys::is_known_key;
+use crate::applications::models::{Detection, GHUBApp};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+static HEX_COLOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$").unwrap());
+
+pub const REGISTRY_HIVES: &[&str] = &[
+    "HKEY_LOCAL_MACHINE",
+    "HKEY_CURRENT_USER",
+    "HKEY_CLASSES_ROOT",
+    "HKEY_USERS",
+    "HKEY_CURRENT_CONFIG",
+    "HKLM",
+    "HKCU",
+    "HKCR",
+    "HKU",
+    "HKCC",
+];
+
+/// Values G HUB understands for `posterTitlePosition`; empty uses its default
+pub const POSTER_TITLE_POSITIONS: &[&str] = &["", "top", "middle", "center", "bottom", "none"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueSeverity {
+    /// The edit is rejected
+    Error,
+    /// Suspicious but allowed, e.g. a key name we don't recognise
+    Warning,
+}
+
+/// A problem with one field, addressed by its JSON path (e.g. `detection[1].steam.appId`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub path: String,
+    pub message: String,
+    pub severity: IssueSeverity,
+}
+
+#[derive(Default)]
+struct Issues {
+    list: Vec<ValidationIssue>,
+    /// The values being checked were already there before the edit
+    unchanged: bool,
+}
+
+impl Issues {
+    /// Errors in unchanged values are only warnings: G HUB ships some of them
+    /// itself and they shouldn't block unrelated edits
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        if self.unchanged {
+            let message = format!("{}; it was already there and is kept as-is", message.into());
+            self.push(path.into(), message, IssueSeverity::Warning);
+        } else {
+            self.push(path.into(), message.into(), IssueSeverity::Error);
+        }
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(path.into(), message.into(), IssueSeverity::Warning);
+    }
+
+    fn push(&mut self, path: String, message: String, severity: IssueSeverity) {
+        self.list.push(ValidationIssue { path, message, severity });
+    }
+
+    /// Run `check` with `unchanged` set
+    fn check(&mut self, unchanged: bool, check: impl FnOnce(&mut Self)) {
+        let previous = std::mem::replace(&mut self.unchanged, unchanged);
+        check(self);
+        self.unchanged = previous;
+    }
+
+    fn non_empty(&mut self, path: String, value: &str) -> bool {
+        if value.trim().is_empty() {
+            self.error(path, "Must not be empty");
+            return false;
+        }
+        true
+    }
+
+    fn numeric(&mut self, path: String, value: &str) {
+        if self.non_empty(path.clone(), value) && !value.trim().chars().all(|c| c.is_ascii_digit()) {
+            self.error(path, format!("'{}' is not a numeric id", value));
+        }
+    }
+}
+
+/// Check an application for values G HUB would reject or silently ignore
+pub fn validate_application(app: &GHUBApp) -> Vec<ValidationIssue> {
+    validate(app, None)
+}
+
+/// Like `validate_application`, but problems with values `before` already had
+/// are warnings, so only new or changed values block an edit
+pub fn validate_edit(before: &GHUBApp, after: &GHUBApp) -> Vec<ValidationIssue> {
+    validate(after, Some(before))
+}
+
+fn validate(app: &GHUBApp, before: Option<&GHUBApp>) -> Vec<ValidationIssue> {
+    let mut issues = Issues::default();
+    let was = |unchanged: &dyn Fn(&GHUBApp) -> bool| before.is_some_and(unchanged);
+
+    issues.check(was(&|b| b.application_id == app.application_id), |issues| {
+        issues.non_empty("applicationId".to_string(), &app.application_id);
+    });
+    issues.check(was(&|b| b.name == app.name), |issues| {
+        issues.non_empty("name".to_string(), &app.name);
+    });
+
+    if !POSTER_TITLE_POSITIONS.contains(&app.poster_title_position.as_str()) {
+        issues.check(was(&|b| b.poster_title_position == app.poster_title_position), |issues| {
+            issues.error(
+                "posterTitlePosition",
+                format!(
+                    "Unknown position '{}', expected one of: {}",
+                    app.poster_title_position,
+                    POSTER_TITLE_POSITIONS[1..].join(", ")
+                ),
+            );
+        });
+    }
+
+    let mut first_tag_index: HashMap<&str, usize> = HashMap::new();
+    for (i, color) in app.category_colors.iter().enumerate() {
+        issues.check(was(&|b| b.category_colors.contains(color)), |issues| {
+            if !HEX_COLOR.is_match(&color.hex) {
+                issues.error(
+                    format!("categoryColors[{}].hex", i),
+                    format!("'{}' is not a hex color like #ff8800", color.hex),
+                );
+            }
+            issues.non_empty(format!("categoryColors[{}].tag", i), &color.tag);
+        });
+        if color.tag.trim().is_empty() {
+            continue;
+        }
+        if let Some(first) = first_tag_index.get(color.tag.as_str()) {
+            // G HUB itself ships applications with a category colored twice
+            let duplicated_before = was(&|b| {
+                b.category_colors.contains(color)
+                    && b.category_colors.iter().filter(|c| c.tag == color.tag).count() > 1
+            });
+            issues.check(duplicated_before, |issues| {
+                issues.error(
+                    format!("categoryColors[{}].tag", i),
+                    format!("Category '{}' already has a color at index {}", color.tag, first),
+                );
+            });
+        } else {
+            first_tag_index.insert(&color.tag, i);
+        }
+    }
+
+    for (i, command) in app.commands.iter().enumerate() {
+        issues.check(was(&|b| b.commands.contains(command)), |issues| {
+            issues.non_empty(format!("commands[{}].name", i), &command.name);
+        });
+        for (j, key) in command.keystroke.iter().enumerate() {
+            if !is_known_key(key) {
+                issues.warning(
+                    format!("commands[{}].keystroke[{}]", i, j),
+                    format!("Unrecognised key name '{}'", key),
+                );
+            }
+        }
+    }
+
+    for (i, detection) in app.detection.iter().enumerate() {
+        issues.check(was(&|b| b.detection.contains(detection)), |issues| {
+            validate_detection(issues, &format!("detection[{}]", i), detection);
+        });
+    }
+
+    issues.list
+}
+
+fn validate_detection(issues: &mut Issues, path: &str, detection: &Detection) {
+    match detection {
+        Detection::Steam { steam } => issues.numeric(format!("{}.steam.appId", path), &steam.app_id),
+        Detection::Uplay { uplay } => issues.numeric(format!("{}.uplay.appId", path), &uplay.app_id),
+        Detection::GogGalaxy { gog_galaxy } => {
+            issues.numeric(format!("{}.gogGalaxy.productId", path), &gog_galaxy.product_id)
+        }
+        Detection::EpicGames { epic_games } => {
+            issues.non_empty(format!("{}.epicGames.appName", path), &epic_games.app_name);
+        }
+        Detection::RiotGames { riot_games } => {
+            issues.non_empty(format!("{}.riotGames.appName", path), &riot_games.app_name);
+        }
+        Detection::WinRegistry { win_registry } => {
+            let registry_path = format!("{}.winRegistry.registryPath", path);
+            if issues.non_empty(registry_path.clone(), &win_registry.registry_path) {
+                let hive = win_registry
+                    .registry_path
+                    .split(['/', '\\'])
+                    .next()
+                    .unwrap_or_default();
+                if !REGISTRY_HIVES.iter().any(|h| h.eq_ignore_ascii_case(hive)) {
+                    issues.error(
+                        registry_path,
+                        format!("'{}' is not a registry hive such as HKEY_LOCAL_MACHINE", hive),
+                    );
+                }
+            }
+            issues.non_empty(format!("{}.winRegistry.registryKey", path), &win_registry.registry_key);
+            issues.non_empty(format!("{}.winRegistry.executable", path), &win_registry.executable);
+        }
+        Detection::OsxBundle { osx_bundle } => {
+            let bundle_id = format!("{}.osxBundle.bundleId", path);
+            if issues.non_empty(bundle_id.clone(), &osx_bundle.bundle_id) && !osx_bundle.bundle_id.contains('.') {
+                issues.error(bundle_id, "Bundle ids are reverse-DNS names like com.example.game");
+            }
+            issues.non_empty(format!("{}.osxBundle.bundlePath", path), &osx_bundle.bundle_path);
+        }
+        Detection::Glob { glob } => {
+            let glob_path = format!("{}.glob", path);
+            if issues.non_empty(glob_path.clone(), glob) {
+                if let Err(e) = glob::Pattern::new(glob) {
+                    issues.error(glob_path, format!("Invalid glob pattern: {}", e));
+                }
+            }
+        }
+        Detection::Unknown(unknown) => match (unknown.malformed_type(), unknown.parse_error()) {
+            (Some(detection_type), Some(error)) => issues.error(
+                format!("{}.{}", path, detection_type.key()),
+                format!("Malformed {} detection: {}", detection_type.key(), error),
+            ),
+            _ => issues.warning(
+                path,
+                match unknown.key() {
+                    Some(key) => format!("Unknown detection type '{}'; it is kept as-is", key),
+                    None => "Detection is not an object; it is kept as-is".to_string(),
+                },
+            ),
+        },
+    }
+}
+
+pub fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(|i| i.severity == IssueSeverity::Error)
+}
+
+/// `Err` listing every error-severity issue, for commands that store applications
+pub fn ensure_valid(app: &GHUBApp) -> Result<(), String> {
+    errors_to_result(validate_application(app))
+}
+
+/// `ensure_valid` for an edit of `before`, see `validate_edit`
+pub fn ensure_valid_edit(before: &GHUBApp, after: &GHUBApp) -> Result<(), String> {
+    errors_to_result(validate_edit(before, after))
+}
+
+fn errors_to_result(issues: Vec<ValidationIssue>) -> Result<(), String> {
+    if !has_errors(&issues) {
+        return Ok(());
+    }
+
+    let errors: Vec<String> = issues
+        .into_iter()
+        .filter(|i| i.severity == IssueSeverity::Error)
+        .map(|i| format!("{}: {}", i.path, i.message))
+        .collect();
+    Err(format!("Invalid application: {}", errors.join("; ")))
+}
+
+/// Validate an application without changing anything, for the editor to highlight fields
+#[tauri::command]
+pub async fn check_application(application: GHUBApp) -> Result<Vec<ValidationIssue>, String> {
+    Ok(validate_application(&application))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn app(value: serde_json::Value) -> GHUBApp {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn error_paths(issues: &[ValidationIssue]) -> Vec<&str> {
+        issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+            .map(|i| i.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn valid_application_has_no_issues() {
+        let issues = validate_application(&app(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "posterTitlePosition": "bottom",
+            "categoryColors": [{ "hex": "#ff8800", "tag": "Combat" }],
+            "commands": [{ "category": "Combat", "keystroke": ["LSHIFT", "F"], "name": "Melee" }],
+            "detection": [
+                { "steam": { "appId": "271590" } },
+                { "winRegistry": { "executable": "GTA5.exe", "registryKey": "InstallFolder",
+                                   "registryPath": "HKEY_LOCAL_MACHINE/SOFTWARE/Rockstar Games" } },
+                { "glob": "C:/Games/**/game.exe" }
+            ]
+        })));
+
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn reports_field_paths_for_each_problem() {
+        let issues = validate_application(&app(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "posterTitlePosition": "sideways",
+            "categoryColors": [
+                { "hex": "red", "tag": "Combat" },
+                { "hex": "#00ff00", "tag": "Combat" }
+            ],
+            "commands": [{ "category": "Combat", "keystroke": ["HYPER"], "name": "Jump" }],
+            "detection": [
+                { "steam": { "appId": "abc" } },
+                { "winRegistry": { "executable": "x.exe", "registryKey": " ", "registryPath": "SOFTWARE/Game" } },
+                { "glob": "C:/Games/[unclosed" }
+            ]
+        })));
+
+        assert_eq!(
+            error_paths(&issues),
+            vec![
+                "posterTitlePosition",
+                "categoryColors[0].hex",
+                "categoryColors[1].tag",
+                "detection[0].steam.appId",
+                "detection[1].winRegistry.registryPath",
+                "detection[1].winRegistry.registryKey",
+                "detection[2].glob",
+            ]
+        );
+        assert!(issues
+            .iter()
+            .any(|i| i.path == "commands[0].keystroke[0]" && i.severity == IssueSeverity::Warning));
+        assert!(has_errors(&issues));
+    }
+
+    #[test]
+    fn malformed_known_detections_are_errors_and_unknown_ones_warnings() {
+        let issues = validate_application(&app(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "detection": [
+                { "steam": { "appid": "440" } },
+                { "eaApp": { "gameId": "1" } }
+            ]
+        })));
+
+        assert_eq!(error_paths(&issues), vec!["detection[0].steam"]);
+        assert!(issues[0].message.contains("appId"), "{}", issues[0].message);
+        assert_eq!(issues[1].path, "detection[1]");
+        assert_eq!(issues[1].severity, IssueSeverity::Warning);
+        assert!(issues[1].message.contains("'eaApp'"));
+    }
+
+    #[test]
+    fn edits_only_fail_on_malformed_detections_they_add_or_change() {
+        let before = app(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "detection": [{ "steam": { "appid": "440" } }]
+        }));
+        let mut renamed = before.clone();
+        renamed.name = "Renamed".to_string();
+        assert!(ensure_valid_edit(&before, &renamed).is_ok());
+        assert!(ensure_valid(&renamed).is_err());
+
+        let mut changed = renamed.clone();
+        changed.detection.push(serde_json::from_value(json!({ "uplay": { "id": "1" } })).unwrap());
+        let error = ensure_valid_edit(&before, &changed).unwrap_err();
+        assert!(error.contains("detection[1].uplay"), "{}", error);
+        assert!(!error.contains("detection[0]"), "{}", error);
+    }
+
+    #[test]
+    fn edits_keep_problems_the_application_already_had() {
+        let before = app(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "posterTitlePosition": "lower-third",
+            "categoryColors": [
+                { "hex": "#ff0000", "tag": "Combat" },
+                { "hex": "#00ff00", "tag": "Combat" }
+            ],
+            "detection": [{ "steam": { "appId": "abc" } }]
+        }));
+        let mut renamed = before.clone();
+        renamed.name = "Renamed".to_string();
+
+        let issues = validate_edit(&before, &renamed);
+        assert!(error_paths(&issues).is_empty(), "{:?}", issues);
+        assert_eq!(
+            issues.iter().map(|i| i.path.as_str()).collect::<Vec<_>>(),
+            vec!["posterTitlePosition", "categoryColors[1].tag", "detection[0].steam.appId"]
+        );
+
+        let mut changed = renamed.clone();
+        changed.poster_title_position = "sideways".to_string();
+        changed.category_colors.push(changed.category_colors[0].clone());
+        changed.category_colors[2].hex = "#0000ff".to_string();
+        assert_eq!(
+            error_paths(&validate_edit(&before, &changed)),
+            vec!["posterTitlePosition", "categoryColors[2].tag"]
+        );
+    }
+}
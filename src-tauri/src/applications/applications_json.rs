@@ -1,15 +1,26 @@
+use crate::applications::app_validation::ensure_valid_edit;
+use crate::applications::backups::create_backup;
+use crate::applications::custom_apps::sync_custom_app_record;
+use crate::applications::journal::{clear_journal, mark_journal_saved, record_edit, EditSource};
 use crate::applications::paths::{get_applications_json_path, get_build_id};
 use crate::core::state::{
-    AppState, store_applications_in_manager, get_stored_applications
+    AppState, store_applications_in_manager, get_stored_applications,
+    store_unparsed_applications, get_unparsed_applications
 };
-use crate::applications::models::{ApplicationsData, GHUBApp};
+use crate::applications::models::{
+    summarize_unknown_detections, ApplicationsLoadReport, GHUBApp, ParsedApplications,
+    UnparsedApplication,
+};
+use serde_json::{Map, Value};
 use std::fs;
+use std::io::Write;
+use std::path::Path;
 use tauri::{AppHandle, Manager, State};
 
 pub fn load_and_store_applications(
     app_handle: &AppHandle,
     build_id: &str,
-) -> Result<Vec<GHUBApp>, String> {
+) -> Result<ApplicationsLoadReport, String> {
     let json_path = get_applications_json_path(app_handle, build_id)
         .ok_or("Failed to get applications.json path")?;
 
@@ -23,9 +34,22 @@ pub fn load_and_store_applications(
     let file_content = fs::read_to_string(&json_path)
         .map_err(|e| format!("Failed to read applications.json: {}", e))?;
 
+    let parsed = parse_applications_json(&file_content)?;
+
+    store_applications_in_manager(app_handle, &parsed.applications)?;
+    store_unparsed_applications(app_handle, &parsed.unparsed)?;
+    // The file on disk is the new baseline, so earlier edits can't be undone
+    clear_journal(app_handle)?;
+
+    Ok(parsed.report())
+}
+
+/// Parse the contents of an applications.json file. Entries that fail to parse
+/// or lack an id or name are kept as raw JSON rather than dropped.
+pub fn parse_applications_json(file_content: &str) -> Result<ParsedApplications, String> {
     // Parse the JSON as a raw value first
-    let json_value: serde_json::Value =
-        serde_json::from_str(&file_content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    let json_value: Value =
+        serde_json::from_str(file_content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
     // Extract the applications array
     let applications_array = json_value
@@ -33,51 +57,86 @@ pub fn load_and_store_applications(
         .and_then(|v| v.as_array())
         .ok_or("No 'applications' array found in JSON")?;
 
-    // Parse each application individually, skipping malformed ones
-    let mut valid_applications = Vec::new();
-    let mut skipped_count = 0;
+    // Parse each application individually, setting malformed ones aside
+    let mut parsed = ParsedApplications::default();
 
     for (index, app_value) in applications_array.iter().enumerate() {
-        match serde_json::from_value::<GHUBApp>(app_value.clone()) {
-            Ok(app) => {
-                // Only include applications that have essential fields
-                if !app.application_id.is_empty() && !app.name.is_empty() {
-                    valid_applications.push(app);
-                } else {
-                    skipped_count += 1;
-                    eprintln!("Skipped application {} (missing essential fields)", index);
-                }
-            }
-            Err(e) => {
-                skipped_count += 1;
-                eprintln!("Failed to parse application {}: {} - skipping", index, e);
+        let reason = match serde_json::from_value::<GHUBApp>(app_value.clone()) {
+            // Only include applications that have essential fields
+            Ok(app) if !app.application_id.is_empty() && !app.name.is_empty() => {
+                parsed.applications.push(app);
+                continue;
             }
-        }
+            Ok(_) => "Missing applicationId or name".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        eprintln!("Skipped application {}: {}", index, reason);
+        parsed.unparsed.push(UnparsedApplication {
+            index,
+            reason,
+            raw: app_value.clone(),
+        });
+    }
+    for summary in summarize_unknown_detections(&parsed.applications) {
+        eprintln!(
+            "{} {} detection(s) of type '{}' kept unchanged",
+            summary.count,
+            if summary.malformed { "malformed" } else { "unknown" },
+            summary.key.as_deref().unwrap_or("<not an object>")
+        );
     }
-    if skipped_count > 0 {
+    if !parsed.unparsed.is_empty() {
         eprintln!(
-            "Loaded {} valid applications, skipped {} malformed entries",
-            valid_applications.len(),
-            skipped_count
+            "Loaded {} valid applications, kept {} malformed entries unchanged",
+            parsed.applications.len(),
+            parsed.unparsed.len()
         );
     } else {
-        println!(
+        eprintln!(
             "Successfully loaded {} applications",
-            valid_applications.len()
+            parsed.applications.len()
         );
     }
 
-    store_applications_in_manager(app_handle, &valid_applications)?;
+    Ok(parsed)
+}
 
-    Ok(valid_applications)
+/// Read and parse an applications.json file (or a backup of one)
+pub fn read_applications_json(path: &Path) -> Result<ParsedApplications, String> {
+    let file_content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_applications_json(&file_content)
+}
+
+/// Serialize applications for writing, putting unparsed entries back at their
+/// original positions so the file keeps its order
+pub fn applications_to_json(
+    applications: &[GHUBApp],
+    unparsed: &[UnparsedApplication],
+) -> Result<Vec<Value>, String> {
+    let mut entries = applications
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<Value>, _>>()
+        .map_err(|e| format!("Failed to serialize applications: {}", e))?;
+
+    let mut unparsed: Vec<&UnparsedApplication> = unparsed.iter().collect();
+    unparsed.sort_by_key(|entry| entry.index);
+    for entry in unparsed {
+        let index = entry.index.min(entries.len());
+        entries.insert(index, entry.raw.clone());
+    }
+
+    Ok(entries)
 }
 
 pub fn initialize_applications_on_startup(app_handle: &AppHandle) -> Result<(), String> {
     // Try to get build_id and load applications
     if let Some(build_id) = get_build_id(app_handle) {
         match load_and_store_applications(app_handle, &build_id) {
-            Ok(apps) => {
-                println!("Successfully loaded {} applications on startup", apps.len());
+            Ok(report) => {
+                eprintln!("Successfully loaded {} applications on startup", report.loaded);
                 Ok(())
             }
             Err(e) => {
@@ -117,13 +176,60 @@ pub async fn update_application(app_handle: AppHandle, updated_app: GHUBApp) ->
             )
         })?;
 
-    apps[app_index] = updated_app;
+    // Keep fields the caller's copy of the application doesn't carry
+    let mut updated_app = updated_app;
+    for (key, value) in &apps[app_index].extra {
+        updated_app.extra.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    ensure_valid_edit(&apps[app_index], &updated_app)?;
+    let before = std::mem::replace(&mut apps[app_index], updated_app);
+    let after = apps[app_index].clone();
+    drop(apps);
+    sync_custom_app_record(&app_handle, &after);
 
-    println!(
+    eprintln!(
         "Successfully updated application with ID: {}",
-        apps[app_index].application_id
+        after.application_id
     );
-    Ok(())
+    let description = format!("Edit {}", after.name);
+    record_edit(&app_handle, EditSource::Editor, &description, Some(before), Some(after))
+}
+
+/// Apply `edit` to a copy of an application and store it if it still passes
+/// validation. Returns the edited application.
+pub fn edit_application<F>(
+    app_handle: &AppHandle,
+    application_id: &str,
+    source: EditSource,
+    description: &str,
+    edit: F,
+) -> Result<GHUBApp, String>
+where
+    F: FnOnce(&mut GHUBApp) -> Result<(), String>,
+{
+    let state: State<AppState> = app_handle.state();
+    let (before, after) = {
+        let mut apps = state
+            .applications
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+        let index = apps
+            .iter()
+            .position(|app| app.application_id == application_id)
+            .ok_or_else(|| format!("Application with ID '{}' not found", application_id))?;
+
+        let mut edited = apps[index].clone();
+        edit(&mut edited)?;
+        ensure_valid_edit(&apps[index], &edited)?;
+        (std::mem::replace(&mut apps[index], edited.clone()), edited)
+    };
+
+    if before != after {
+        sync_custom_app_record(app_handle, &after);
+        record_edit(app_handle, source, description, Some(before), Some(after.clone()))?;
+    }
+    Ok(after)
 }
 
 #[tauri::command]
@@ -151,33 +257,321 @@ pub async fn save_applications_to_disk(app_handle: AppHandle) -> Result<(), Stri
     let json_path = get_applications_json_path(&app_handle, &build_id)
         .ok_or("Failed to get applications.json path")?;
 
+    // Keep a copy of the file we are about to overwrite
+    create_backup(&app_handle, &build_id)?;
+
+    let unparsed = get_unparsed_applications(&app_handle)?;
     let state: State<AppState> = app_handle.state();
     let apps = state
         .applications
         .lock()
         .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
 
-    let applications_data = ApplicationsData {
-        applications: apps.clone(),
-    };
+    let mut file_data = read_top_level_extra(&json_path);
+    file_data.insert(
+        "applications".to_string(),
+        Value::Array(applications_to_json(&apps, &unparsed)?),
+    );
+
+    let json_content = serde_json::to_string_pretty(&file_data)
+        .map_err(|e| format!("Failed to serialize applications: {}", e))?;
+
+    write_applications_json(&json_path, &json_content, apps.len() + unparsed.len())?;
+    // Still holding the applications lock, so no edit can slip in unsaved
+    mark_journal_saved(&app_handle)?;
+
+    eprintln!(
+        "Successfully saved {} applications to disk ({} unparsed entries kept)",
+        apps.len(),
+        unparsed.len()
+    );
+    Ok(())
+}
+
+/// Add applications to the end of applications.json without writing any other
+/// pending edit, e.g. for newly created custom applications
+pub fn append_applications_to_disk(app_handle: &AppHandle, new_apps: &[GHUBApp]) -> Result<(), String> {
+    let build_id = get_build_id(app_handle).ok_or("Failed to get build_id")?;
+    let json_path = get_applications_json_path(app_handle, &build_id)
+        .ok_or("Failed to get applications.json path")?;
+
+    create_backup(app_handle, &build_id)?;
+
+    let file_content = fs::read_to_string(&json_path)
+        .map_err(|e| format!("Failed to read applications.json: {}", e))?;
+    let (json_content, count) = append_applications(&file_content, new_apps)?;
+    write_applications_json(&json_path, &json_content, count)?;
+
+    eprintln!("Appended {} applications to applications.json", new_apps.len());
+    Ok(())
+}
+
+/// `file_content` with `new_apps` added to its `applications` array, and the
+/// resulting number of entries. Everything else is kept as it was.
+pub fn append_applications(file_content: &str, new_apps: &[GHUBApp]) -> Result<(String, usize), String> {
+    let mut file_data: Map<String, Value> = serde_json::from_str(file_content)
+        .map_err(|e| format!("Failed to parse applications.json: {}", e))?;
+
+    let entries = file_data
+        .entry("applications")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or("applications.json has no applications array")?;
+    for app in new_apps {
+        entries.push(serde_json::to_value(app).map_err(|e| format!("Failed to serialize application: {}", e))?);
+    }
+    let count = entries.len();
 
-    let json_content = serde_json::to_string_pretty(&applications_data)
+    let json_content = serde_json::to_string_pretty(&file_data)
         .map_err(|e| format!("Failed to serialize applications: {}", e))?;
+    Ok((json_content, count))
+}
+
+/// Applications loaded from disk and the entries that were skipped
+#[tauri::command]
+pub async fn get_applications_load_report(app_handle: AppHandle) -> Result<ApplicationsLoadReport, String> {
+    let applications = get_stored_applications(&app_handle)?;
+    let unparsed = get_unparsed_applications(&app_handle)?;
+    Ok(ApplicationsLoadReport::new(&applications, &unparsed))
+}
+
+/// Top-level fields of the existing file other than `applications`, so saving
+/// doesn't drop anything G HUB stores alongside the list
+fn read_top_level_extra(json_path: &Path) -> Map<String, Value> {
+    fs::read_to_string(json_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|value| match value {
+            Value::Object(mut fields) => {
+                fields.remove("applications");
+                Some(fields)
+            }
+            _ => None,
+        })
+        .unwrap_or_default()
+}
 
+/// Atomically replace applications.json: write to a temporary file next to it,
+/// check that it parses back with `expected_count` entries, then rename over
+/// the original. A crash mid-write leaves the original file untouched.
+pub fn write_applications_json(
+    json_path: &Path,
+    json_content: &str,
+    expected_count: usize,
+) -> Result<(), String> {
     // Create parent directory if it doesn't exist
     if let Some(parent) = json_path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
     }
 
-    fs::write(&json_path, json_content)
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                format!("Permission denied writing to {}: {}. Please run as administrator or check folder permissions.", json_path.display(), e)
-            } else {
-                format!("Failed to write applications.json: {}", e)
-            }
-        })?;
+    let temp_path = json_path.with_extension("json.tmp");
+    let permission_error = |e: std::io::Error, action: &str| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            format!("Permission denied writing to {}: {}. Please run as administrator or check folder permissions.", json_path.display(), e)
+        } else {
+            format!("Failed to {}: {}", action, e)
+        }
+    };
+
+    if let Err(e) = write_synced(&temp_path, json_content) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(permission_error(e, "write applications.json"));
+    }
+
+    if let Err(e) = verify_applications_file(&temp_path, expected_count) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    fs::rename(&temp_path, json_path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        permission_error(e, "replace applications.json")
+    })
+}
+
+fn write_synced(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()
+}
+
+/// Read a written file back and make sure every entry survived
+fn verify_applications_file(path: &Path, expected_count: usize) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read back written applications.json: {}", e))?;
+    let written = parse_applications_json(&content)
+        .map_err(|e| format!("Written applications.json does not parse: {}", e))?;
+
+    if written.total() != expected_count {
+        return Err(format!(
+            "Written applications.json contains {} applications, expected {}",
+            written.total(),
+            expected_count
+        ));
+    }
 
-    println!("Successfully saved {} applications to disk", apps.len());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::models::{ApplicationsData, Detection, DetectionType};
+    use serde_json::json;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("g-hauler-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unknown_fields_round_trip() {
+        let original = json!({
+            "schemaVersion": 3,
+            "applications": [{
+                "applicationId": "a",
+                "name": "Game",
+                "databaseId": "db-1",
+                "isCustom": true,
+                "detection": [{ "glob": "C:/Games/game.exe" }]
+            }]
+        });
+
+        let data: ApplicationsData = serde_json::from_value(original.clone()).unwrap();
+        assert_eq!(data.extra["schemaVersion"], 3);
+        assert_eq!(data.applications[0].extra["databaseId"], "db-1");
+
+        let written = serde_json::to_value(&data).unwrap();
+        assert_eq!(written["schemaVersion"], 3);
+        assert_eq!(written["applications"][0]["databaseId"], "db-1");
+        assert_eq!(written["applications"][0]["isCustom"], true);
+    }
+
+    #[test]
+    fn unknown_detections_are_summarised_and_written_back_exactly() {
+        let content = r#"{"applications":[
+            {"applicationId":"a","name":"A","detection":[{"eaApp":{"zeta":1,"alpha":[2,1]}},{"steam":{"appId":"1"}}]},
+            {"applicationId":"b","name":"B","detection":[{"eaApp":{"gameId":"x"}},{"steam":{"appid":"2"}}]}
+        ]}"#;
+
+        let parsed = parse_applications_json(content).unwrap();
+        let summary = parsed.report().unknown_detections;
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].key.as_deref(), Some("eaApp"));
+        assert_eq!((summary[0].count, summary[0].malformed), (2, false));
+        assert_eq!(summary[0].application_ids, vec!["a", "b"]);
+        assert_eq!(summary[1].key.as_deref(), Some("steam"));
+        assert!(summary[1].malformed);
+
+        let Detection::Unknown(unknown) = &parsed.applications[0].detection[0] else {
+            panic!("expected an unknown detection");
+        };
+        assert_eq!(unknown.key(), Some("eaApp"));
+        let written = serde_json::to_string(&parsed.applications[0].detection[0]).unwrap();
+        assert_eq!(written, r#"{"eaApp":{"zeta":1,"alpha":[2,1]}}"#);
+    }
+
+    #[test]
+    fn known_detections_with_extra_fields_round_trip_exactly() {
+        let detections = [
+            r#"{"steam":{"appId":"1","x":1}}"#,
+            r#"{"steam":{"appId":"1"},"extra":true}"#,
+        ];
+        for detection in detections {
+            let parsed: Detection = serde_json::from_str(detection).unwrap();
+            let Detection::Unknown(unknown) = &parsed else {
+                panic!("{} should be kept as an unknown detection", detection);
+            };
+            assert_eq!(unknown.malformed_type(), Some(DetectionType::Steam));
+            assert!(unknown.parse_error().is_some());
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), detection);
+        }
+
+        let sibling: Detection = serde_json::from_str(detections[1]).unwrap();
+        let Detection::Unknown(unknown) = sibling else { unreachable!() };
+        assert_eq!(unknown.parse_error().as_deref(), Some("unexpected fields next to the detection"));
+
+        let clean: Detection = serde_json::from_str(r#"{"steam":{"appId":"1"}}"#).unwrap();
+        assert!(matches!(clean, Detection::Steam { .. }));
+    }
+
+    #[test]
+    fn appending_keeps_the_rest_of_the_file_as_is() {
+        let content = r#"{"version":3,"applications":[{"applicationId":"a","name":"A","zeta":1,"alpha":2}]}"#;
+        let new_app: GHUBApp = serde_json::from_value(json!({ "applicationId": "b", "name": "B" })).unwrap();
+
+        let (written, count) = append_applications(content, &[new_app]).unwrap();
+
+        assert_eq!(count, 2);
+        let written: Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(written["version"], 3);
+        assert_eq!(
+            serde_json::to_string(&written["applications"][0]).unwrap(),
+            r#"{"applicationId":"a","name":"A","zeta":1,"alpha":2}"#
+        );
+        assert_eq!(written["applications"][1]["applicationId"], "b");
+    }
+
+    #[test]
+    fn unparseable_entries_are_kept_and_written_back_in_place() {
+        let content = json!({
+            "applications": [
+                { "applicationId": "a", "name": "A" },
+                { "applicationId": "b", "name": "B", "version": "not a number" },
+                { "applicationId": "", "name": "Nameless id" },
+                { "applicationId": "c", "name": "C" }
+            ]
+        })
+        .to_string();
+
+        let parsed = parse_applications_json(&content).unwrap();
+        assert_eq!(parsed.applications.len(), 2);
+        assert_eq!(parsed.total(), 4);
+
+        let report = parsed.report();
+        assert_eq!(report.loaded, 2);
+        assert_eq!(
+            report.skipped.iter().map(|s| s.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(report.skipped[0].application_id.as_deref(), Some("b"));
+
+        let entries = applications_to_json(&parsed.applications, &parsed.unparsed).unwrap();
+        let original: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[1], original["applications"][1]);
+        assert_eq!(entries[2], original["applications"][2]);
+        assert_eq!(entries[3]["applicationId"], "c");
+    }
+
+    #[test]
+    fn atomic_write_replaces_file_and_cleans_up() {
+        let dir = temp_dir("atomic-write");
+        let path = dir.join("applications.json");
+        fs::write(&path, r#"{"applications":[]}"#).unwrap();
+
+        let content = r#"{"applications":[{"applicationId":"a","name":"A"}]}"#;
+        write_applications_json(&path, content, 1).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_verification_leaves_original_untouched() {
+        let dir = temp_dir("atomic-verify");
+        let path = dir.join("applications.json");
+        let original = r#"{"applications":[]}"#;
+        fs::write(&path, original).unwrap();
+
+        let result = write_applications_json(&path, "{ not json", 0);
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::applications::applications_json::{
+    load_and_store_applications, parse_applications_json, write_applications_json,
+};
+use crate::applications::ghub_version::get_ghub_version;
+use crate::applications::diff::{
+    backup_applications, build_applications, diff_applications_detailed, ApplicationsDiff,
+};
+use crate::applications::paths::{get_applications_json_path, get_build_id};
+use crate::core::constants::{BACKUPS_DIRNAME, DEFAULT_BACKUP_RETENTION, STORE_KEY_BACKUP_RETENTION};
+use crate::core::store::get_store_key;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Metadata saved next to each backup of applications.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    /// Timestamp-based identifier, also the backup's file stem
+    pub id: String,
+    pub created_at: String,
+    pub build_id: String,
+    pub ghub_version: Option<String>,
+    pub application_count: usize,
+    pub size_bytes: u64,
+}
+
+/// Get the directory holding applications.json backups
+pub fn get_backups_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    let backups_dir = app_data_dir.join(BACKUPS_DIRNAME);
+
+    // Ensure the directory exists
+    if !backups_dir.exists() {
+        fs::create_dir_all(&backups_dir)
+            .map_err(|e| format!("Failed to create backups directory: {}", e))?;
+    }
+
+    Ok(backups_dir)
+}
+
+fn backup_file_path(backups_dir: &Path, id: &str) -> PathBuf {
+    backups_dir.join(format!("{}.json", id))
+}
+
+fn backup_meta_path(backups_dir: &Path, id: &str) -> PathBuf {
+    backups_dir.join(format!("{}.meta.json", id))
+}
+
+/// Number of backups to keep, from the settings registry
+fn get_retention(app_handle: &AppHandle) -> usize {
+    get_store_key(app_handle, STORE_KEY_BACKUP_RETENTION)
+        .and_then(|v| v.as_f64())
+        .map(|n| n.max(1.0) as usize)
+        .unwrap_or(DEFAULT_BACKUP_RETENTION as usize)
+}
+
+/// Copy the current applications.json for `build_id` into the backups directory.
+/// Returns `None` if there is no file to back up yet.
+pub fn create_backup(app_handle: &AppHandle, build_id: &str) -> Result<Option<BackupInfo>, String> {
+    create_backup_keeping(app_handle, build_id, None)
+}
+
+/// `create_backup`, but never pruning the backup `keep`
+fn create_backup_keeping(
+    app_handle: &AppHandle,
+    build_id: &str,
+    keep: Option<&str>,
+) -> Result<Option<BackupInfo>, String> {
+    let json_path = get_applications_json_path(app_handle, build_id)
+        .ok_or("Failed to get applications.json path")?;
+
+    if !json_path.exists() {
+        return Ok(None);
+    }
+
+    let file_content = fs::read_to_string(&json_path)
+        .map_err(|e| format!("Failed to read applications.json for backup: {}", e))?;
+    let application_count = parse_applications_json(&file_content)
+        .map(|parsed| parsed.total())
+        .unwrap_or(0);
+
+    let backups_dir = get_backups_dir(app_handle)?;
+    let now = chrono::Utc::now();
+    let id = now.format("%Y%m%d-%H%M%S-%3f").to_string();
+
+    let info = BackupInfo {
+        id: id.clone(),
+        created_at: now.to_rfc3339(),
+        build_id: build_id.to_string(),
+        ghub_version: get_ghub_version(app_handle),
+        application_count,
+        size_bytes: file_content.len() as u64,
+    };
+
+    fs::write(backup_file_path(&backups_dir, &id), &file_content)
+        .map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    let meta_content = serde_json::to_string_pretty(&info)
+        .map_err(|e| format!("Failed to serialize backup metadata: {}", e))?;
+    fs::write(backup_meta_path(&backups_dir, &id), meta_content)
+        .map_err(|e| format!("Failed to write backup metadata: {}", e))?;
+
+    prune_backups(app_handle, get_retention(app_handle), keep)?;
+
+    eprintln!("Backed up applications.json to {}", id);
+    Ok(Some(info))
+}
+
+/// List backups, newest first
+pub fn list_backups(app_handle: &AppHandle) -> Result<Vec<BackupInfo>, String> {
+    let backups_dir = get_backups_dir(app_handle)?;
+
+    let entries = fs::read_dir(&backups_dir)
+        .map_err(|e| format!("Failed to read backups directory: {}", e))?;
+
+    let mut backups: Vec<BackupInfo> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.to_string_lossy().ends_with(".meta.json"))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str::<BackupInfo>(&content).ok())
+        .collect();
+
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(backups)
+}
+
+/// Ids of backups to delete so that only the newest `retention` remain, apart
+/// from `keep` which is never deleted
+fn backups_to_prune(mut ids: Vec<String>, retention: usize, keep: Option<&str>) -> Vec<String> {
+    ids.sort_by(|a, b| b.cmp(a));
+    ids.into_iter()
+        .skip(retention)
+        .filter(|id| Some(id.as_str()) != keep)
+        .collect()
+}
+
+fn prune_backups(app_handle: &AppHandle, retention: usize, keep: Option<&str>) -> Result<(), String> {
+    let backups_dir = get_backups_dir(app_handle)?;
+    let ids = list_backups(app_handle)?.into_iter().map(|b| b.id).collect();
+
+    for id in backups_to_prune(ids, retention, keep) {
+        let _ = fs::remove_file(backup_file_path(&backups_dir, &id));
+        let _ = fs::remove_file(backup_meta_path(&backups_dir, &id));
+    }
+
+    Ok(())
+}
+
+/// Path of a backup's copy of applications.json
+pub fn get_backup_path(app_handle: &AppHandle, backup_id: &str) -> Result<PathBuf, String> {
+    find_backup(app_handle, backup_id).map(|(_, path)| path)
+}
+
+fn find_backup(app_handle: &AppHandle, backup_id: &str) -> Result<(BackupInfo, PathBuf), String> {
+    let info = list_backups(app_handle)?
+        .into_iter()
+        .find(|b| b.id == backup_id)
+        .ok_or_else(|| format!("Backup '{}' not found", backup_id))?;
+    let path = backup_file_path(&get_backups_dir(app_handle)?, backup_id);
+    Ok((info, path))
+}
+
+#[tauri::command]
+pub async fn backup_list(app_handle: AppHandle) -> Result<Vec<BackupInfo>, String> {
+    list_backups(&app_handle)
+}
+
+#[tauri::command]
+pub async fn backup_create(app_handle: AppHandle) -> Result<Option<BackupInfo>, String> {
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+    create_backup(&app_handle, &build_id)
+}
+
+/// Compare a backup against the current applications.json on disk
+#[tauri::command]
+pub async fn backup_diff_with_current(
+    app_handle: AppHandle,
+    backup_id: String,
+) -> Result<ApplicationsDiff, String> {
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+    let backup_apps = backup_applications(&app_handle, &backup_id)?;
+    let current_apps = build_applications(&app_handle, &build_id)?;
+
+    Ok(diff_applications_detailed(&backup_apps, &current_apps))
+}
+
+/// Restore a backup over the current applications.json and reload it.
+/// The current file is backed up first so a restore can itself be undone.
+#[tauri::command]
+pub async fn backup_restore(
+    app_handle: AppHandle,
+    backup_id: String,
+    allow_other_build: Option<bool>,
+) -> Result<(), String> {
+    let (info, backup_path) = find_backup(&app_handle, &backup_id)?;
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+
+    if info.build_id != build_id && !allow_other_build.unwrap_or(false) {
+        return Err(format!(
+            "Backup '{}' was taken from build {} but the current build is {}",
+            backup_id, info.build_id, build_id
+        ));
+    }
+
+    let json_path = get_applications_json_path(&app_handle, &build_id)
+        .ok_or("Failed to get applications.json path")?;
+
+    // Read the backup before taking the safety backup, and keep it when pruning
+    let backup_content = fs::read_to_string(&backup_path)
+        .map_err(|e| format!("Failed to read backup: {}", e))?;
+    let application_count = parse_applications_json(&backup_content)?.total();
+
+    create_backup_keeping(&app_handle, &build_id, Some(&backup_id))?;
+
+    write_applications_json(&json_path, &backup_content, application_count)?;
+
+    load_and_store_applications(&app_handle, &build_id)?;
+
+    eprintln!("Restored applications.json from backup {}", backup_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_keeps_newest_backups() {
+        let ids = vec![
+            "20250101-000000-000".to_string(),
+            "20250103-000000-000".to_string(),
+            "20250102-000000-000".to_string(),
+        ];
+
+        let pruned = backups_to_prune(ids.clone(), 2, None);
+
+        assert_eq!(pruned, vec!["20250101-000000-000".to_string()]);
+        assert!(backups_to_prune(ids, 2, Some("20250101-000000-000")).is_empty());
+    }
+
+    #[test]
+    fn prune_with_room_to_spare_deletes_nothing() {
+        let ids = vec!["20250101-000000-000".to_string()];
+        assert!(backups_to_prune(ids, 10, None).is_empty());
+    }
+}
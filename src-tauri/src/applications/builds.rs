@@ -0,0 +1,161 @@
+use crate::applications::applications_json::read_applications_json;
+use crate::applications::ghub_version::{get_ghub_version, read_version_json};
+use crate::applications::models::{ApplicationsLoadReport, GHUBApp, ParsedApplications};
+use crate::applications::paths::{get_applications_json_path, get_build_id, get_depots_dir_path};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// A build directory under `<data>/depots`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub build_id: String,
+    pub path: String,
+    /// Whether current.json points at this build
+    pub is_current: bool,
+    pub version: Option<String>,
+    /// Last modification time of the build directory (RFC 3339)
+    pub modified_at: Option<String>,
+    pub has_applications_json: bool,
+    /// `None` when applications.json is missing or doesn't parse
+    pub load_report: Option<ApplicationsLoadReport>,
+    /// Why applications.json couldn't be read, if it exists but failed
+    pub error: Option<String>,
+}
+
+/// Build ids are plain directory names; reject anything that could escape `depots`
+fn is_valid_build_id(build_id: &str) -> bool {
+    !build_id.is_empty()
+        && build_id != "."
+        && build_id != ".."
+        && !build_id.contains(['/', '\\'])
+}
+
+/// Version recorded inside a build directory, if it has a version.json of its own
+fn read_build_version(build_dir: &Path) -> Option<String> {
+    read_version_json(&build_dir.join("version.json")).ok().map(|v| v.version)
+}
+
+/// Every build directory under `<data>/depots`, current build first, then newest first
+pub fn list_builds(app_handle: &AppHandle) -> Result<Vec<BuildInfo>, String> {
+    let depots_dir = get_depots_dir_path(app_handle).ok_or("G HUB data path is not set")?;
+    let current_build_id = get_build_id(app_handle);
+
+    let entries = fs::read_dir(&depots_dir)
+        .map_err(|e| format!("Failed to read depots directory: {}", e))?;
+
+    let mut builds: Vec<BuildInfo> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| {
+            let build_id = entry.file_name().to_string_lossy().to_string();
+            let build_dir = entry.path();
+            let is_current = current_build_id.as_deref() == Some(build_id.as_str());
+
+            let version = read_build_version(&build_dir).or_else(|| {
+                // The top-level version.json describes the current build
+                if is_current {
+                    get_ghub_version(app_handle)
+                } else {
+                    None
+                }
+            });
+            let modified_at = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339());
+
+            let json_path = get_applications_json_path(app_handle, &build_id);
+            let has_applications_json = json_path.as_ref().is_some_and(|p| p.exists());
+            let (load_report, error) = match json_path.filter(|_| has_applications_json) {
+                Some(path) => match read_applications_json(&path) {
+                    Ok(parsed) => (Some(parsed.report()), None),
+                    Err(e) => (None, Some(e)),
+                },
+                None => (None, None),
+            };
+
+            BuildInfo {
+                build_id,
+                path: build_dir.to_string_lossy().to_string(),
+                is_current,
+                version,
+                modified_at,
+                has_applications_json,
+                load_report,
+                error,
+            }
+        })
+        .collect();
+
+    builds.sort_by(|a, b| {
+        b.is_current
+            .cmp(&a.is_current)
+            .then_with(|| b.modified_at.cmp(&a.modified_at))
+    });
+
+    Ok(builds)
+}
+
+/// Read (never write) the applications.json of any build under `depots`
+pub fn read_build_applications(app_handle: &AppHandle, build_id: &str) -> Result<ParsedApplications, String> {
+    if !is_valid_build_id(build_id) {
+        return Err(format!("Invalid build id '{}'", build_id));
+    }
+
+    let json_path = get_applications_json_path(app_handle, build_id)
+        .ok_or("Failed to get applications.json path")?;
+
+    if !json_path.exists() {
+        return Err(format!(
+            "Build {} has no applications.json at: {}",
+            build_id,
+            json_path.display()
+        ));
+    }
+
+    read_applications_json(&json_path)
+}
+
+#[tauri::command]
+pub async fn builds_list(app_handle: AppHandle) -> Result<Vec<BuildInfo>, String> {
+    list_builds(&app_handle)
+}
+
+/// Applications of a build, read-only; they are not loaded into the editor
+#[tauri::command]
+pub async fn build_get_applications(
+    app_handle: AppHandle,
+    build_id: String,
+) -> Result<Vec<GHUBApp>, String> {
+    Ok(read_build_applications(&app_handle, &build_id)?.applications)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_ids_cannot_escape_depots() {
+        assert!(is_valid_build_id("2024.5.123456"));
+        assert!(!is_valid_build_id(""));
+        assert!(!is_valid_build_id(".."));
+        assert!(!is_valid_build_id("../secrets"));
+        assert!(!is_valid_build_id("a\\b"));
+    }
+
+    #[test]
+    fn build_version_is_read_from_the_build_directory() {
+        let dir = std::env::temp_dir().join(format!("g-hauler-build-version-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(read_build_version(&dir), None);
+
+        fs::write(dir.join("version.json"), r#"{"version":"2025.3.1"}"#).unwrap();
+        assert_eq!(read_build_version(&dir).as_deref(), Some("2025.3.1"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::applications::app_validation::ensure_valid_edit;
+use crate::applications::diff::{diff_application, FieldChange};
+use crate::applications::journal::{record_edits, EditSource};
+use crate::applications::live_sync::ApplicationsChanged;
+use crate::applications::models::{CategoryColor, Detection, DetectionType, GHUBApp};
+use crate::applications::query::{run_query, ApplicationQuery};
+use crate::core::state::AppState;
+use crate::ghub_game_patches::persistence::load_applied_patches;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// A transformation applied to every selected application
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "op")]
+pub enum BulkOperation {
+    /// Replace text in the string fields of detections, optionally only of one type
+    ReplaceInDetections {
+        find: String,
+        replace: String,
+        #[serde(default)]
+        detection_type: Option<DetectionType>,
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+    /// Set the color of these categories where an application has them
+    SetCategoryColors { colors: Vec<CategoryColor> },
+    /// Add a glob detection; `{name}` and `{applicationId}` are substituted
+    AddGlobDetection { pattern: String },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BulkEditRequest {
+    /// Which applications to consider; paging is ignored
+    pub query: ApplicationQuery,
+    /// Further limit the selection to these ids
+    pub application_ids: Option<Vec<String>>,
+    pub operations: Vec<BulkOperation>,
+    /// Only report what would change
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditChange {
+    pub application_id: String,
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+    /// Set when the result fails validation; the application is left as it was
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditReport {
+    /// Applications matched by the selection
+    pub matched: usize,
+    pub changed: Vec<BulkEditChange>,
+    pub applied: bool,
+}
+
+fn detection_strings_mut(detection: &mut Detection) -> Vec<&mut String> {
+    match detection {
+        Detection::Steam { steam } => vec![&mut steam.app_id],
+        Detection::WinRegistry { win_registry } => vec![
+            &mut win_registry.executable,
+            &mut win_registry.registry_key,
+            &mut win_registry.registry_path,
+        ],
+        Detection::EpicGames { epic_games } => vec![&mut epic_games.app_name],
+        Detection::OsxBundle { osx_bundle } => vec![&mut osx_bundle.bundle_id, &mut osx_bundle.bundle_path],
+        Detection::Uplay { uplay } => vec![&mut uplay.app_id],
+        Detection::GogGalaxy { gog_galaxy } => vec![&mut gog_galaxy.product_id],
+        Detection::RiotGames { riot_games } => vec![&mut riot_games.app_name],
+        Detection::Glob { glob } => vec![glob],
+        Detection::Unknown(_) => Vec::new(),
+    }
+}
+
+fn replace_text(value: &str, find: &str, replace: &str, case_insensitive: bool) -> String {
+    if !case_insensitive {
+        return value.replace(find, replace);
+    }
+
+    // Lowercasing ASCII keeps byte offsets, so matches map back onto `value`
+    let haystack = value.to_ascii_lowercase();
+    let needle = find.to_ascii_lowercase();
+    let mut result = String::with_capacity(value.len());
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        result.push_str(&value[last..start]);
+        result.push_str(replace);
+        last = start + needle.len();
+    }
+    result.push_str(&value[last..]);
+    result
+}
+
+pub fn apply_operation(app: &mut GHUBApp, operation: &BulkOperation) {
+    match operation {
+        BulkOperation::ReplaceInDetections {
+            find,
+            replace,
+            detection_type,
+            case_insensitive,
+        } => {
+            if find.is_empty() {
+                return;
+            }
+            for detection in app
+                .detection
+                .iter_mut()
+                .filter(|d| detection_type.is_none() || d.detection_type() == *detection_type)
+            {
+                for value in detection_strings_mut(detection) {
+                    *value = replace_text(value, find, replace, *case_insensitive);
+                }
+            }
+        }
+        BulkOperation::SetCategoryColors { colors } => {
+            for new_color in colors {
+                for color in app.category_colors.iter_mut().filter(|c| c.tag == new_color.tag) {
+                    color.hex = new_color.hex.clone();
+                }
+            }
+        }
+        BulkOperation::AddGlobDetection { pattern } => {
+            let glob = pattern
+                .replace("{name}", &app.name)
+                .replace("{applicationId}", &app.application_id);
+            let exists = app
+                .detection
+                .iter()
+                .any(|d| matches!(d, Detection::Glob { glob: existing } if *existing == glob));
+            if !exists {
+                app.detection.push(Detection::Glob { glob });
+            }
+        }
+    }
+}
+
+/// Run the operations over the selected applications. Returns the report and
+/// the edited copies of applications that changed and passed validation.
+pub fn plan_bulk_edit(
+    apps: &[GHUBApp],
+    request: &BulkEditRequest,
+    patched_ids: &HashSet<String>,
+) -> (BulkEditReport, Vec<GHUBApp>) {
+    let query = ApplicationQuery {
+        offset: 0,
+        limit: None,
+        ..request.query.clone()
+    };
+    let selected: Vec<GHUBApp> = run_query(apps, &query, patched_ids)
+        .items
+        .into_iter()
+        .filter(|app| {
+            request
+                .application_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&app.application_id))
+        })
+        .collect();
+
+    let mut report = BulkEditReport {
+        matched: selected.len(),
+        ..Default::default()
+    };
+    let mut edited_apps = Vec::new();
+
+    for original in &selected {
+        let mut edited = original.clone();
+        for operation in &request.operations {
+            apply_operation(&mut edited, operation);
+        }
+
+        let changes = diff_application(original, &edited);
+        if changes.is_empty() {
+            continue;
+        }
+
+        let error = ensure_valid_edit(original, &edited).err();
+        if error.is_none() {
+            edited_apps.push(edited);
+        }
+        report.changed.push(BulkEditChange {
+            application_id: original.application_id.clone(),
+            name: original.name.clone(),
+            changes,
+            error,
+        });
+    }
+
+    (report, edited_apps)
+}
+
+/// Apply declarative operations to a filtered set of applications, or preview them with `dryRun`
+#[tauri::command]
+pub async fn bulk_edit_applications(
+    app_handle: AppHandle,
+    request: BulkEditRequest,
+) -> Result<BulkEditReport, String> {
+    let patched_ids: HashSet<String> = if request.query.patched.is_some() {
+        load_applied_patches(&app_handle)?
+            .per_game_overrides
+            .into_iter()
+            .map(|o| o.application_id)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let state: State<AppState> = app_handle.state();
+    let (mut report, replaced) = {
+        let mut apps = state
+            .applications
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+        let (report, edited_apps) = plan_bulk_edit(&apps, &request, &patched_ids);
+        let mut replaced = Vec::new();
+        if !request.dry_run {
+            for edited in edited_apps {
+                if let Some(app) = apps.iter_mut().find(|a| a.application_id == edited.application_id) {
+                    replaced.push((std::mem::replace(app, edited.clone()), edited));
+                }
+            }
+        }
+        (report, replaced)
+    };
+
+    if request.dry_run {
+        return Ok(report);
+    }
+
+    let modified: Vec<String> = replaced.iter().map(|(_, after)| after.application_id.clone()).collect();
+    let description = match replaced.as_slice() {
+        [(_, after)] => format!("Bulk edit {}", after.name),
+        _ => format!("Bulk edit {} applications", replaced.len()),
+    };
+    let changes = replaced
+        .into_iter()
+        .map(|(before, after)| (Some(before), Some(after)))
+        .collect();
+    record_edits(&app_handle, EditSource::Editor, &description, changes)?;
+    if !modified.is_empty() {
+        let _ = app_handle.emit(
+            "applications-changed",
+            ApplicationsChanged {
+                modified,
+                ..Default::default()
+            },
+        );
+    }
+
+    report.applied = true;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn apps() -> Vec<GHUBApp> {
+        [
+            json!({ "applicationId": "a", "name": "Alpha",
+                    "categoryColors": [{ "hex": "#000000", "tag": "Combat" }],
+                    "detection": [{ "winRegistry": { "executable": "alpha.exe", "registryKey": "Path",
+                                                     "registryPath": "HKEY_LOCAL_MACHINE/SOFTWARE/OldVendor/Alpha" } }] }),
+            json!({ "applicationId": "b", "name": "Beta",
+                    "detection": [{ "steam": { "appId": "440" } }] }),
+        ]
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect()
+    }
+
+    fn request(operations: Vec<BulkOperation>) -> BulkEditRequest {
+        BulkEditRequest {
+            operations,
+            dry_run: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replaces_text_in_detection_fields() {
+        let (report, edited) = plan_bulk_edit(
+            &apps(),
+            &request(vec![BulkOperation::ReplaceInDetections {
+                find: "oldvendor".to_string(),
+                replace: "NewVendor".to_string(),
+                detection_type: Some(DetectionType::WinRegistry),
+                case_insensitive: true,
+            }]),
+            &HashSet::new(),
+        );
+
+        assert_eq!(report.matched, 2);
+        assert_eq!(report.changed.len(), 1);
+        assert_eq!(report.changed[0].application_id, "a");
+        let Detection::WinRegistry { win_registry } = &edited[0].detection[0] else {
+            panic!("expected a registry detection");
+        };
+        assert_eq!(win_registry.registry_path, "HKEY_LOCAL_MACHINE/SOFTWARE/NewVendor/Alpha");
+    }
+
+    #[test]
+    fn adds_glob_detections_and_sets_colors_for_the_selection() {
+        let mut request = request(vec![
+            BulkOperation::AddGlobDetection { pattern: "C:/Games/{name}/*.exe".to_string() },
+            BulkOperation::SetCategoryColors {
+                colors: vec![CategoryColor { hex: "#ff0000".to_string(), tag: "Combat".to_string() }],
+            },
+        ]);
+        request.application_ids = Some(vec!["a".to_string()]);
+
+        let (report, edited) = plan_bulk_edit(&apps(), &request, &HashSet::new());
+
+        assert_eq!(report.matched, 1);
+        assert!(matches!(&edited[0].detection[1], Detection::Glob { glob } if glob == "C:/Games/Alpha/*.exe"));
+        assert_eq!(edited[0].category_colors[0].hex, "#ff0000");
+    }
+
+    #[test]
+    fn invalid_results_are_reported_and_not_applied() {
+        let (report, edited) = plan_bulk_edit(
+            &apps(),
+            &request(vec![BulkOperation::AddGlobDetection { pattern: "C:/[{name}".to_string() }]),
+            &HashSet::new(),
+        );
+
+        assert_eq!(report.changed.len(), 2);
+        assert!(report.changed.iter().all(|c| c.error.is_some()));
+        assert!(edited.is_empty());
+    }
+}
@@ -0,0 +1,594 @@
+use crate::applications::app_validation::{ensure_valid, ensure_valid_edit};
+use crate::applications::ghub_version::get_ghub_version;
+use crate::applications::journal::{record_edits, EditSource};
+use crate::applications::live_sync::ApplicationsChanged;
+use crate::applications::models::GHUBApp;
+use crate::applications::paths::{get_build_id, get_images_dir_path};
+use crate::applications::posters::{is_safe_relative_path, poster_relative_path};
+use crate::core::state::{get_stored_applications, AppState};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const BUNDLE_FORMAT: &str = "g-hauler-bundle";
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// A poster image carried inside a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledImage {
+    /// Path relative to the G HUB images directory, with `/` separators
+    pub path: String,
+    /// Base64-encoded file contents
+    pub data: String,
+}
+
+/// Portable set of application definitions and their poster images, saved as one JSON file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationBundle {
+    pub format: String,
+    pub format_version: u32,
+    pub exported_at: String,
+    pub ghub_version: Option<String>,
+    pub applications: Vec<GHUBApp>,
+    #[serde(default)]
+    pub images: Vec<BundledImage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleExportSummary {
+    pub path: String,
+    pub application_count: usize,
+    pub image_count: usize,
+    /// Poster URLs that pointed into the images directory but had no file
+    pub missing_images: Vec<String>,
+}
+
+/// What to do when an imported application clashes with an existing one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictResolution {
+    /// Leave the existing application alone
+    #[default]
+    Skip,
+    /// Overwrite the existing application with the imported one
+    Replace,
+    /// Import alongside it, with a new id (id clash) or a suffixed name (name clash)
+    KeepBoth,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImportOptions {
+    /// Same `application_id` as an existing application
+    pub on_id_conflict: ConflictResolution,
+    /// Different id but the same name (case-insensitive)
+    pub on_name_conflict: ConflictResolution,
+    /// Report what would happen without changing anything
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    Id,
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportConflict {
+    /// Id in the bundle
+    pub application_id: String,
+    pub name: String,
+    pub kind: ConflictKind,
+    /// Id of the existing application it clashed with
+    pub existing_application_id: String,
+    pub resolution: ConflictResolution,
+}
+
+/// An application left out of an import because it doesn't pass validation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidImport {
+    pub application_id: String,
+    pub name: String,
+    pub reason: String,
+}
+
+/// A bundled image written under a new name because a different file already
+/// had its path; imported poster URLs were pointed at the new path
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedImage {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// Ids added to the list (after any re-generation)
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+    /// Ids left out because of a conflict or, see `invalid`, validation
+    pub skipped: Vec<String>,
+    pub invalid: Vec<InvalidImport>,
+    pub conflicts: Vec<ImportConflict>,
+    pub images_written: Vec<String>,
+    /// Images left alone because the same file already exists at that path
+    pub images_kept: Vec<String>,
+    pub images_renamed: Vec<RenamedImage>,
+}
+
+/// A decoded bundle image and the path it will be written to
+#[derive(Debug)]
+struct PreparedImage {
+    path: String,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct ImagePlan {
+    to_write: Vec<PreparedImage>,
+    kept: Vec<String>,
+    renamed: Vec<RenamedImage>,
+}
+
+/// Merge imported applications into `apps` according to `options`.
+/// Returns the report and the `(before, after)` pairs of every change made.
+pub fn merge_applications(
+    apps: &mut Vec<GHUBApp>,
+    incoming: Vec<GHUBApp>,
+    options: &ImportOptions,
+) -> (ImportReport, Vec<(Option<GHUBApp>, GHUBApp)>) {
+    let mut report = ImportReport::default();
+    let mut changes = Vec::new();
+
+    for mut app in incoming {
+        // Problems the local copy already has, e.g. ones G HUB ships, don't count
+        let validation = match apps.iter().find(|a| a.application_id == app.application_id) {
+            Some(existing) => ensure_valid_edit(existing, &app),
+            None => ensure_valid(&app),
+        };
+        if let Err(reason) = validation {
+            report.skipped.push(app.application_id.clone());
+            report.invalid.push(InvalidImport {
+                application_id: app.application_id,
+                name: app.name,
+                reason,
+            });
+            continue;
+        }
+
+        let id_clash = apps.iter().position(|a| a.application_id == app.application_id);
+        let name_clash = apps
+            .iter()
+            .position(|a| a.application_id != app.application_id && a.name.eq_ignore_ascii_case(&app.name));
+
+        let (kind, index, resolution) = match (id_clash, name_clash) {
+            (Some(index), _) => (ConflictKind::Id, index, options.on_id_conflict),
+            (None, Some(index)) => (ConflictKind::Name, index, options.on_name_conflict),
+            (None, None) => {
+                report.added.push(app.application_id.clone());
+                changes.push((None, app.clone()));
+                apps.push(app);
+                continue;
+            }
+        };
+
+        report.conflicts.push(ImportConflict {
+            application_id: app.application_id.clone(),
+            name: app.name.clone(),
+            kind,
+            existing_application_id: apps[index].application_id.clone(),
+            resolution,
+        });
+
+        match resolution {
+            ConflictResolution::Skip => report.skipped.push(app.application_id.clone()),
+            ConflictResolution::Replace => {
+                // Keep the existing id so references to it stay valid
+                app.application_id = apps[index].application_id.clone();
+                report.replaced.push(app.application_id.clone());
+                let before = std::mem::replace(&mut apps[index], app.clone());
+                changes.push((Some(before), app));
+            }
+            ConflictResolution::KeepBoth => {
+                if kind == ConflictKind::Id {
+                    app.application_id = uuid::Uuid::new_v4().to_string();
+                }
+                if apps.iter().any(|a| a.name.eq_ignore_ascii_case(&app.name)) {
+                    app.name = unique_name(apps, &app.name);
+                }
+                report.added.push(app.application_id.clone());
+                changes.push((None, app.clone()));
+                apps.push(app);
+            }
+        }
+    }
+
+    (report, changes)
+}
+
+/// Decode every bundled image and settle where it goes, without writing anything.
+/// Identical files already in `images_dir` are kept; an image whose path holds a
+/// different file gets a free name instead.
+fn plan_images(images_dir: &Path, images: &[BundledImage]) -> Result<ImagePlan, String> {
+    let mut plan = ImagePlan::default();
+    let mut taken: HashSet<String> = HashSet::new();
+
+    for image in images {
+        if !is_safe_relative_path(Path::new(&image.path)) {
+            return Err(format!("Bundle contains an invalid image path '{}'", image.path));
+        }
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&image.data)
+            .map_err(|e| format!("Failed to decode image '{}': {}", image.path, e))?;
+
+        let is_free = |path: &str, taken: &HashSet<String>| !taken.contains(path) && !images_dir.join(path).exists();
+        let path = match fs::read(images_dir.join(&image.path)) {
+            Ok(existing) if existing == bytes => {
+                plan.kept.push(image.path.clone());
+                continue;
+            }
+            Ok(_) => {
+                let path = free_image_path(&image.path, |candidate| is_free(candidate, &taken));
+                plan.renamed.push(RenamedImage {
+                    from: image.path.clone(),
+                    to: path.clone(),
+                });
+                path
+            }
+            Err(_) => image.path.clone(),
+        };
+        taken.insert(path.clone());
+        plan.to_write.push(PreparedImage { path, bytes });
+    }
+
+    Ok(plan)
+}
+
+/// `posters/game-imported.jpg`, `posters/game-imported-2.jpg`, ... whichever is free first
+fn free_image_path(path: &str, is_free: impl Fn(&str) -> bool) -> String {
+    let (stem, extension) = match path.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => (stem, format!(".{}", extension)),
+        _ => (path, String::new()),
+    };
+    let mut candidate = format!("{}-imported{}", stem, extension);
+    let mut n = 2;
+    while !is_free(&candidate) {
+        candidate = format!("{}-imported-{}{}", stem, n, extension);
+        n += 1;
+    }
+    candidate
+}
+
+/// Point poster URLs of imported applications at their renamed images
+fn rewrite_poster_urls(apps: &mut [GHUBApp], renamed: &[RenamedImage]) {
+    for app in apps {
+        let Some(relative) = poster_relative_path(&app.poster_url) else {
+            continue;
+        };
+        let bundle_path = relative.to_string_lossy().replace('\\', "/");
+        if let Some(rename) = renamed.iter().find(|rename| rename.from == bundle_path) {
+            app.poster_url = format!("file://images/{}", rename.to);
+        }
+    }
+}
+
+/// Write planned images, removing the ones already written if any write fails
+fn write_images(images_dir: &Path, images: &[PreparedImage]) -> Result<(), String> {
+    let mut written: Vec<PathBuf> = Vec::new();
+    for image in images {
+        let target = images_dir.join(&image.path);
+        let result = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&target, &image.bytes));
+        if let Err(e) = result {
+            for path in &written {
+                let _ = fs::remove_file(path);
+            }
+            return Err(format!("Failed to write image '{}': {}", image.path, e));
+        }
+        written.push(target);
+    }
+    Ok(())
+}
+
+/// `name (imported)`, `name (imported 2)`, ... whichever is free first
+fn unique_name(apps: &[GHUBApp], name: &str) -> String {
+    let taken = |candidate: &str| apps.iter().any(|a| a.name.eq_ignore_ascii_case(candidate));
+    let mut candidate = format!("{} (imported)", name);
+    let mut n = 2;
+    while taken(&candidate) {
+        candidate = format!("{} (imported {})", name, n);
+        n += 1;
+    }
+    candidate
+}
+
+fn current_images_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let build_id = get_build_id(app_handle).ok_or("Failed to get build_id")?;
+    get_images_dir_path(app_handle, &build_id).ok_or_else(|| "Failed to get images directory path".to_string())
+}
+
+/// Collect the poster images referenced by `apps` from the images directory
+fn collect_images(images_dir: &Path, apps: &[GHUBApp]) -> (Vec<BundledImage>, Vec<String>) {
+    let mut images: Vec<BundledImage> = Vec::new();
+    let mut missing = Vec::new();
+
+    for app in apps {
+        let Some(relative) = poster_relative_path(&app.poster_url) else {
+            continue;
+        };
+        let bundle_path = relative.to_string_lossy().replace('\\', "/");
+        if images.iter().any(|image| image.path == bundle_path) {
+            continue;
+        }
+
+        match fs::read(images_dir.join(&relative)) {
+            Ok(bytes) => images.push(BundledImage {
+                path: bundle_path,
+                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            }),
+            Err(_) => missing.push(app.poster_url.clone()),
+        }
+    }
+
+    (images, missing)
+}
+
+/// Export the selected applications and their poster images to a bundle file
+#[tauri::command]
+pub async fn bundle_export(
+    app_handle: AppHandle,
+    application_ids: Vec<String>,
+    path: String,
+) -> Result<BundleExportSummary, String> {
+    let applications: Vec<GHUBApp> = get_stored_applications(&app_handle)?
+        .into_iter()
+        .filter(|app| application_ids.contains(&app.application_id))
+        .collect();
+
+    if applications.is_empty() {
+        return Err("None of the selected applications were found".to_string());
+    }
+
+    let (images, missing_images) = match current_images_dir(&app_handle) {
+        Ok(images_dir) => collect_images(&images_dir, &applications),
+        Err(_) => (Vec::new(), Vec::new()),
+    };
+
+    let bundle = ApplicationBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        format_version: BUNDLE_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        ghub_version: get_ghub_version(&app_handle),
+        applications,
+        images,
+    };
+
+    let json_content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+    fs::write(&path, json_content).map_err(|e| format!("Failed to write bundle: {}", e))?;
+
+    eprintln!("Exported {} applications to {}", bundle.applications.len(), path);
+    Ok(BundleExportSummary {
+        path,
+        application_count: bundle.applications.len(),
+        image_count: bundle.images.len(),
+        missing_images,
+    })
+}
+
+/// Import a bundle into the in-memory application list and copy its poster
+/// images into the images directory. Save to write the applications to disk.
+#[tauri::command]
+pub async fn bundle_import(
+    app_handle: AppHandle,
+    path: String,
+    options: ImportOptions,
+) -> Result<ImportReport, String> {
+    let file_content = fs::read_to_string(&path).map_err(|e| format!("Failed to read bundle: {}", e))?;
+    let bundle: ApplicationBundle =
+        serde_json::from_str(&file_content).map_err(|e| format!("Failed to parse bundle: {}", e))?;
+
+    if bundle.format != BUNDLE_FORMAT || bundle.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported bundle format '{}' version {}",
+            bundle.format, bundle.format_version
+        ));
+    }
+
+    // Every image is decoded before anything changes, so a bad one fails the
+    // whole import; images are written before the applications are merged
+    let images_dir = current_images_dir(&app_handle)?;
+    let plan = plan_images(&images_dir, &bundle.images)?;
+    let mut applications = bundle.applications;
+    rewrite_poster_urls(&mut applications, &plan.renamed);
+    if !options.dry_run {
+        write_images(&images_dir, &plan.to_write)?;
+    }
+
+    let state: State<AppState> = app_handle.state();
+    let (mut report, changes) = {
+        let mut apps = state
+            .applications
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+        if options.dry_run {
+            let mut preview = apps.clone();
+            merge_applications(&mut preview, applications, &options)
+        } else {
+            merge_applications(&mut apps, applications, &options)
+        }
+    };
+    report.images_written = plan.to_write.into_iter().map(|image| image.path).collect();
+    report.images_kept = plan.kept;
+    report.images_renamed = plan.renamed;
+
+    if !options.dry_run && !changes.is_empty() {
+        let description = match changes.as_slice() {
+            [(_, app)] => format!("Import {}", app.name),
+            _ => format!("Import {} applications", changes.len()),
+        };
+        let changes = changes.into_iter().map(|(before, after)| (before, Some(after))).collect();
+        record_edits(&app_handle, EditSource::Import, &description, changes)?;
+        let _ = app_handle.emit(
+            "applications-changed",
+            ApplicationsChanged {
+                added: report.added.clone(),
+                modified: report.replaced.clone(),
+                ..Default::default()
+            },
+        );
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn app(id: &str, name: &str) -> GHUBApp {
+        serde_json::from_value(json!({ "applicationId": id, "name": name })).unwrap()
+    }
+
+    #[test]
+    fn new_applications_are_added() {
+        let mut apps = vec![app("a", "A")];
+
+        let (report, changes) = merge_applications(&mut apps, vec![app("b", "B")], &ImportOptions::default());
+
+        assert_eq!(report.added, vec!["b"]);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(apps.len(), 2);
+    }
+
+    #[test]
+    fn invalid_applications_are_skipped() {
+        let mut apps = vec![app("a", "A")];
+
+        let (report, changes) = merge_applications(
+            &mut apps,
+            vec![app("", "No id"), app("c", " "), app("d", "D")],
+            &ImportOptions::default(),
+        );
+
+        assert_eq!(report.added, vec!["d"]);
+        assert_eq!(report.skipped, vec!["", "c"]);
+        assert_eq!(report.invalid.len(), 2);
+        assert!(report.invalid[0].reason.contains("applicationId"), "{}", report.invalid[0].reason);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(apps.len(), 2);
+
+        // A copy of a local application with the same G HUB-made problem is fine
+        let duplicated: GHUBApp = serde_json::from_value(json!({
+            "applicationId": "e",
+            "name": "E",
+            "categoryColors": [{ "hex": "#ff0000", "tag": "Combat" }, { "hex": "#00ff00", "tag": "Combat" }]
+        }))
+        .unwrap();
+        let mut apps = vec![duplicated.clone()];
+        let replace = ImportOptions {
+            on_id_conflict: ConflictResolution::Replace,
+            ..Default::default()
+        };
+        let (report, _) = merge_applications(&mut apps, vec![duplicated], &replace);
+        assert_eq!(report.replaced, vec!["e"]);
+    }
+
+    #[test]
+    fn id_conflicts_follow_the_chosen_resolution() {
+        let incoming = vec![app("a", "A v2")];
+
+        let mut apps = vec![app("a", "A")];
+        let (report, _) = merge_applications(&mut apps, incoming.clone(), &ImportOptions::default());
+        assert_eq!(report.skipped, vec!["a"]);
+        assert_eq!(apps[0].name, "A");
+
+        let replace = ImportOptions {
+            on_id_conflict: ConflictResolution::Replace,
+            ..Default::default()
+        };
+        let (report, _) = merge_applications(&mut apps, incoming.clone(), &replace);
+        assert_eq!(report.replaced, vec!["a"]);
+        assert_eq!(apps[0].name, "A v2");
+
+        let keep_both = ImportOptions {
+            on_id_conflict: ConflictResolution::KeepBoth,
+            ..Default::default()
+        };
+        let (report, _) = merge_applications(&mut apps, incoming, &keep_both);
+        assert_eq!(apps.len(), 2);
+        assert_ne!(report.added[0], "a");
+        assert_eq!(apps[1].name, "A v2 (imported)");
+    }
+
+    #[test]
+    fn clashing_images_are_renamed_and_posters_follow() {
+        let dir = std::env::temp_dir().join(format!("g-hauler-bundle-images-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("posters")).unwrap();
+        fs::write(dir.join("posters/same.jpg"), b"same").unwrap();
+        fs::write(dir.join("posters/other.jpg"), b"theirs").unwrap();
+        fs::write(dir.join("posters/other-imported.jpg"), b"taken too").unwrap();
+
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+        let image = |path: &str, bytes: &[u8]| BundledImage { path: path.to_string(), data: encode(bytes) };
+        let images = [image("posters/same.jpg", b"same"), image("posters/other.jpg", b"ours"), image("new.png", b"new")];
+
+        let plan = plan_images(&dir, &images).unwrap();
+        assert_eq!(plan.kept, vec!["posters/same.jpg"]);
+        assert_eq!(
+            plan.renamed,
+            vec![RenamedImage {
+                from: "posters/other.jpg".to_string(),
+                to: "posters/other-imported-2.jpg".to_string(),
+            }]
+        );
+        let paths: Vec<&str> = plan.to_write.iter().map(|image| image.path.as_str()).collect();
+        assert_eq!(paths, vec!["posters/other-imported-2.jpg", "new.png"]);
+
+        let mut apps = vec![app("a", "A"), app("b", "B")];
+        apps[0].poster_url = "file://images/posters/other.jpg".to_string();
+        apps[1].poster_url = "file://images/posters/same.jpg".to_string();
+        rewrite_poster_urls(&mut apps, &plan.renamed);
+        assert_eq!(apps[0].poster_url, "file://images/posters/other-imported-2.jpg");
+        assert_eq!(apps[1].poster_url, "file://images/posters/same.jpg");
+
+        // Nothing is planned, let alone written, when any image is broken
+        let broken = [image("ok.png", b"ok"), BundledImage { path: "bad.png".to_string(), data: "!".to_string() }];
+        assert!(plan_images(&dir, &broken).unwrap_err().contains("bad.png"));
+        assert!(!dir.join("ok.png").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn name_conflicts_are_detected_case_insensitively() {
+        let mut apps = vec![app("a", "Fortnite")];
+        let options = ImportOptions {
+            on_name_conflict: ConflictResolution::Replace,
+            ..Default::default()
+        };
+
+        let (report, _) = merge_applications(&mut apps, vec![app("b", "FORTNITE")], &options);
+
+        assert_eq!(report.conflicts[0].kind, ConflictKind::Name);
+        assert_eq!(report.conflicts[0].existing_application_id, "a");
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].application_id, "a");
+        assert_eq!(apps[0].name, "FORTNITE");
+    }
+}
@@ -0,0 +1,404 @@
+use crate::applications::applications_json::edit_application;
+use crate::applications::journal::EditSource;
+use crate::applications::keys::is_known_key;
+use crate::applications::models::{CategoryColor, Command, GHUBApp};
+use crate::core::state::get_stored_applications;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use tauri::AppHandle;
+
+/// Colors given to new categories, in order, skipping ones already in use
+const CATEGORY_PALETTE: &[&str] = &[
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6", "#bfef45",
+    "#fabed4", "#469990", "#dcbeff", "#9a6324",
+];
+
+const COMMAND_SET_FORMAT: &str = "g-hauler-commands";
+const COMMAND_SET_FORMAT_VERSION: u32 = 1;
+
+/// One change to an application's command list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "op")]
+pub enum CommandEdit {
+    /// Insert at `index`, or append
+    Add { command: Command, index: Option<usize> },
+    Update { index: usize, command: Command },
+    Move { from: usize, to: usize },
+    Delete { index: usize },
+    RenameCategory { from: String, to: String },
+    /// Remove a category together with its commands
+    DeleteCategory { name: String },
+    /// Only for categories that have commands
+    SetCategoryColor { category: String, hex: String },
+}
+
+/// Commands and category colors that can be copied between games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSet {
+    pub format: String,
+    pub format_version: u32,
+    pub commands: Vec<Command>,
+    pub category_colors: Vec<CategoryColor>,
+}
+
+impl CommandSet {
+    pub fn from_app(app: &GHUBApp) -> Self {
+        CommandSet {
+            format: COMMAND_SET_FORMAT.to_string(),
+            format_version: COMMAND_SET_FORMAT_VERSION,
+            commands: app.commands.clone(),
+            category_colors: app.category_colors.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportMode {
+    /// Replace the target's commands and colors
+    #[default]
+    Replace,
+    /// Add commands whose name and category aren't there yet
+    Merge,
+}
+
+fn check_keystroke(command: &Command) -> Result<(), String> {
+    if command.name.trim().is_empty() {
+        return Err("Command name must not be empty".to_string());
+    }
+    let unknown: Vec<&str> = command
+        .keystroke
+        .iter()
+        .map(String::as_str)
+        .filter(|key| !is_known_key(key))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Unknown keys in '{}': {}", command.name, unknown.join(", ")))
+    }
+}
+
+fn check_index(app: &GHUBApp, index: usize) -> Result<(), String> {
+    if index < app.commands.len() {
+        Ok(())
+    } else {
+        Err(format!("No command at index {}", index))
+    }
+}
+
+pub fn apply_command_edit(app: &mut GHUBApp, edit: CommandEdit) -> Result<(), String> {
+    match edit {
+        CommandEdit::Add { command, index } => {
+            check_keystroke(&command)?;
+            let index = index.unwrap_or(app.commands.len()).min(app.commands.len());
+            app.commands.insert(index, command);
+        }
+        CommandEdit::Update { index, command } => {
+            check_index(app, index)?;
+            check_keystroke(&command)?;
+            app.commands[index] = command;
+        }
+        CommandEdit::Move { from, to } => {
+            check_index(app, from)?;
+            check_index(app, to)?;
+            let command = app.commands.remove(from);
+            app.commands.insert(to, command);
+        }
+        CommandEdit::Delete { index } => {
+            check_index(app, index)?;
+            app.commands.remove(index);
+        }
+        CommandEdit::RenameCategory { from, to } => {
+            if to.trim().is_empty() {
+                return Err("Category name must not be empty".to_string());
+            }
+            if from != to && app.category_colors.iter().any(|c| c.tag == to) {
+                return Err(format!("Category '{}' already exists", to));
+            }
+            for command in app.commands.iter_mut().filter(|c| c.category == from) {
+                command.category = to.clone();
+            }
+            for color in app.category_colors.iter_mut().filter(|c| c.tag == from) {
+                color.tag = to.clone();
+            }
+        }
+        CommandEdit::DeleteCategory { name } => {
+            app.commands.retain(|c| c.category != name);
+            app.category_colors.retain(|c| c.tag != name);
+        }
+        CommandEdit::SetCategoryColor { category, hex } => {
+            // `sync_category_colors` drops colors of categories without commands
+            if !app.commands.iter().any(|c| c.category == category) {
+                return Err(format!("No command uses category '{}'", category));
+            }
+            match app.category_colors.iter_mut().find(|c| c.tag == category) {
+                Some(color) => color.hex = hex,
+                None => app.category_colors.push(CategoryColor { hex, tag: category }),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Drop colors of categories no command uses and give new categories a color
+pub fn sync_category_colors(app: &mut GHUBApp) {
+    let mut in_use: Vec<&str> = Vec::new();
+    for command in &app.commands {
+        if !command.category.is_empty() && !in_use.contains(&command.category.as_str()) {
+            in_use.push(&command.category);
+        }
+    }
+
+    let mut colors: Vec<CategoryColor> = app
+        .category_colors
+        .iter()
+        .filter(|c| in_use.contains(&c.tag.as_str()))
+        .cloned()
+        .collect();
+
+    for category in in_use {
+        if colors.iter().any(|c| c.tag == category) {
+            continue;
+        }
+        let used: HashSet<String> = colors.iter().map(|c| c.hex.to_lowercase()).collect();
+        let hex = CATEGORY_PALETTE
+            .iter()
+            .find(|hex| !used.contains(**hex))
+            .unwrap_or(&CATEGORY_PALETTE[colors.len() % CATEGORY_PALETTE.len()]);
+        colors.push(CategoryColor {
+            hex: hex.to_string(),
+            tag: category.to_string(),
+        });
+    }
+
+    app.category_colors = colors;
+}
+
+/// Copy a command set into an application
+pub fn import_command_set(app: &mut GHUBApp, set: &CommandSet, mode: ImportMode) -> Result<(), String> {
+    for command in &set.commands {
+        check_keystroke(command)?;
+    }
+
+    match mode {
+        ImportMode::Replace => {
+            app.commands = set.commands.clone();
+            app.category_colors = set.category_colors.clone();
+        }
+        ImportMode::Merge => {
+            for command in &set.commands {
+                let exists = app
+                    .commands
+                    .iter()
+                    .any(|c| c.name == command.name && c.category == command.category);
+                if !exists {
+                    app.commands.push(command.clone());
+                }
+            }
+            for color in &set.category_colors {
+                if !app.category_colors.iter().any(|c| c.tag == color.tag) {
+                    app.category_colors.push(color.clone());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply command and category edits to an application, in order
+#[tauri::command]
+pub async fn command_library_edit(
+    app_handle: AppHandle,
+    application_id: String,
+    edits: Vec<CommandEdit>,
+) -> Result<GHUBApp, String> {
+    edit_application(&app_handle, &application_id, EditSource::Editor, "Edit commands", |app| {
+        for edit in edits {
+            apply_command_edit(app, edit)?;
+        }
+        sync_category_colors(app);
+        Ok(())
+    })
+}
+
+/// Write an application's commands and category colors to a file
+#[tauri::command]
+pub async fn command_library_export(
+    app_handle: AppHandle,
+    application_id: String,
+    path: String,
+) -> Result<CommandSet, String> {
+    let apps = get_stored_applications(&app_handle)?;
+    let app = apps
+        .iter()
+        .find(|a| a.application_id == application_id)
+        .ok_or_else(|| format!("Application with ID '{}' not found", application_id))?;
+
+    let set = CommandSet::from_app(app);
+    let json = serde_json::to_string_pretty(&set)
+        .map_err(|e| format!("Failed to serialize command set: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write command set: {}", e))?;
+    Ok(set)
+}
+
+/// Load a command set file into an application
+#[tauri::command]
+pub async fn command_library_import(
+    app_handle: AppHandle,
+    application_id: String,
+    path: String,
+    mode: ImportMode,
+) -> Result<GHUBApp, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read command set: {}", e))?;
+    let set: CommandSet =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse command set: {}", e))?;
+    if set.format != COMMAND_SET_FORMAT || set.format_version > COMMAND_SET_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported command set format '{}' version {}",
+            set.format, set.format_version
+        ));
+    }
+
+    edit_application(&app_handle, &application_id, EditSource::Import, "Import commands", |app| {
+        import_command_set(app, &set, mode)?;
+        sync_category_colors(app);
+        Ok(())
+    })
+}
+
+/// Copy commands and category colors from one application to another
+#[tauri::command]
+pub async fn command_library_copy(
+    app_handle: AppHandle,
+    from_application_id: String,
+    to_application_id: String,
+    mode: ImportMode,
+) -> Result<GHUBApp, String> {
+    let apps = get_stored_applications(&app_handle)?;
+    let source = apps
+        .iter()
+        .find(|a| a.application_id == from_application_id)
+        .ok_or_else(|| format!("Application with ID '{}' not found", from_application_id))?;
+    let set = CommandSet::from_app(source);
+    let description = format!("Copy commands from {}", source.name);
+
+    edit_application(&app_handle, &to_application_id, EditSource::Editor, &description, |app| {
+        import_command_set(app, &set, mode)?;
+        sync_category_colors(app);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn app() -> GHUBApp {
+        serde_json::from_value(json!({
+            "applicationId": "a",
+            "name": "Game",
+            "categoryColors": [
+                { "hex": "#ff0000", "tag": "Combat" },
+                { "hex": "#00ff00", "tag": "Unused" }
+            ],
+            "commands": [
+                { "category": "Combat", "keystroke": ["F"], "name": "Melee" },
+                { "category": "Combat", "keystroke": ["G"], "name": "Grenade" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn command(category: &str, name: &str, keys: &[&str]) -> Command {
+        Command {
+            category: category.to_string(),
+            keystroke: keys.iter().map(|k| k.to_string()).collect(),
+            name: name.to_string(),
+        }
+    }
+
+    fn names(app: &GHUBApp) -> Vec<&str> {
+        app.commands.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn edits_reorder_and_delete_commands() {
+        let mut app = app();
+        let edits = vec![
+            CommandEdit::Add { command: command("Movement", "Jump", &["SPACE"]), index: Some(0) },
+            CommandEdit::Move { from: 2, to: 0 },
+            CommandEdit::Delete { index: 2 },
+        ];
+        for edit in edits {
+            apply_command_edit(&mut app, edit).unwrap();
+        }
+
+        assert_eq!(names(&app), vec!["Grenade", "Jump"]);
+        assert!(apply_command_edit(&mut app, CommandEdit::Delete { index: 5 }).is_err());
+    }
+
+    #[test]
+    fn category_colors_can_only_be_set_for_used_categories() {
+        let mut app = app();
+
+        let result = apply_command_edit(
+            &mut app,
+            CommandEdit::SetCategoryColor { category: "Movement".to_string(), hex: "#0000ff".to_string() },
+        );
+        assert!(result.unwrap_err().contains("Movement"));
+
+        let edits = vec![
+            CommandEdit::Add { command: command("Movement", "Jump", &["SPACE"]), index: None },
+            CommandEdit::SetCategoryColor { category: "Movement".to_string(), hex: "#0000ff".to_string() },
+        ];
+        for edit in edits {
+            apply_command_edit(&mut app, edit).unwrap();
+        }
+        sync_category_colors(&mut app);
+
+        let movement = app.category_colors.iter().find(|c| c.tag == "Movement").unwrap();
+        assert_eq!(movement.hex, "#0000ff");
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let mut app = app();
+        let result = apply_command_edit(
+            &mut app,
+            CommandEdit::Update { index: 0, command: command("Combat", "Melee", &["HYPER"]) },
+        );
+
+        assert!(result.unwrap_err().contains("HYPER"));
+        assert_eq!(app.commands[0].keystroke, vec!["F"]);
+    }
+
+    #[test]
+    fn category_colors_follow_categories_in_use() {
+        let mut app = app();
+        apply_command_edit(&mut app, CommandEdit::RenameCategory { from: "Combat".into(), to: "Fight".into() })
+            .unwrap();
+        app.commands.push(command("Movement", "Jump", &["SPACE"]));
+
+        sync_category_colors(&mut app);
+
+        let tags: Vec<&str> = app.category_colors.iter().map(|c| c.tag.as_str()).collect();
+        assert_eq!(tags, vec!["Fight", "Movement"]);
+        assert_eq!(app.category_colors[0].hex, "#ff0000");
+        assert_eq!(app.category_colors[1].hex, CATEGORY_PALETTE[0]);
+    }
+
+    #[test]
+    fn merge_import_skips_existing_commands() {
+        let mut target = app();
+        let mut source = app();
+        source.commands.push(command("Movement", "Jump", &["SPACE"]));
+
+        import_command_set(&mut target, &CommandSet::from_app(&source), ImportMode::Merge).unwrap();
+
+        assert_eq!(names(&target), vec!["Melee", "Grenade", "Jump"]);
+    }
+}
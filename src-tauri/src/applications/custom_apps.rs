@@ -0,0 +1,374 @@
+use crate::applications::app_validation::ensure_valid;
+use crate::applications::applications_json::append_applications_to_disk;
+use crate::applications::journal::{record_edit, EditSource};
+use crate::applications::models::{CategoryColor, Command, Detection, GHUBApp};
+use crate::core::constants::CUSTOM_APPS_FILENAME;
+use crate::core::state::{get_stored_applications, AppState};
+use crate::websocket::client::{WebSocketClient, WebSocketMessage};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+/// Where a custom application is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CustomAppTarget {
+    /// Sent to the running G HUB over the WebSocket
+    GHub,
+    /// Added to applications.json on disk
+    ApplicationsJson,
+}
+
+/// Fields the user provides for a new application; the id is generated
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomAppRequest {
+    pub name: String,
+    pub detection: Vec<Detection>,
+    #[serde(default)]
+    pub poster_url: String,
+    #[serde(default)]
+    pub poster_title_position: Option<String>,
+    #[serde(default)]
+    pub category_colors: Vec<CategoryColor>,
+    #[serde(default)]
+    pub commands: Vec<Command>,
+}
+
+/// A g-hauler-created application, kept so it can be re-created after a G HUB update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomAppRecord {
+    pub application: GHUBApp,
+    pub target: CustomAppTarget,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomAppsData {
+    pub applications: Vec<CustomAppRecord>,
+}
+
+/// Build a `GHUBApp` from a request with a freshly generated application id
+pub fn build_custom_app(request: CustomAppRequest) -> GHUBApp {
+    GHUBApp {
+        application_id: uuid::Uuid::new_v4().to_string(),
+        category_colors: request.category_colors,
+        commands: request.commands,
+        detection: request.detection,
+        name: request.name.trim().to_string(),
+        poster_title_position: request
+            .poster_title_position
+            .unwrap_or_else(|| "bottom".to_string()),
+        poster_url: request.poster_url,
+        version: 1,
+        extra: Default::default(),
+    }
+}
+
+/// Check a new application against the existing ones, returning every problem found
+pub fn validate_custom_app(app: &GHUBApp, existing: &[GHUBApp]) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    if app.name.is_empty() {
+        errors.push("Name is required".to_string());
+    } else if existing.iter().any(|a| a.name.eq_ignore_ascii_case(&app.name)) {
+        errors.push(format!("An application named '{}' already exists", app.name));
+    }
+
+    if existing.iter().any(|a| a.application_id == app.application_id) {
+        errors.push(format!("Application id '{}' is already in use", app.application_id));
+    }
+
+    if app.detection.is_empty() {
+        errors.push("At least one detection is required".to_string());
+    }
+    if app.detection.iter().any(|d| matches!(d, Detection::Unknown(_))) {
+        errors.push("Detections must be one of the supported types".to_string());
+    }
+
+    let mut tags = HashSet::new();
+    for color in &app.category_colors {
+        if !tags.insert(color.tag.as_str()) {
+            errors.push(format!("Category '{}' has more than one color", color.tag));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Get the path to the custom_apps.json file
+pub fn get_custom_apps_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    // Ensure the directory exists
+    if !app_data_dir.exists() {
+        std::fs::create_dir_all(&app_data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+
+    Ok(app_data_dir.join(CUSTOM_APPS_FILENAME))
+}
+
+pub fn load_custom_apps(app_handle: &AppHandle) -> Result<CustomAppsData, String> {
+    let path = get_custom_apps_path(app_handle)?;
+
+    if !path.exists() {
+        return Ok(CustomAppsData::default());
+    }
+
+    let file_content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read custom_apps.json: {}", e))?;
+
+    serde_json::from_str(&file_content).map_err(|e| format!("Failed to parse custom_apps.json: {}", e))
+}
+
+pub fn save_custom_apps(app_handle: &AppHandle, data: &CustomAppsData) -> Result<(), String> {
+    let path = get_custom_apps_path(app_handle)?;
+
+    let json_content = serde_json::to_string_pretty(data)
+        .map_err(|e| format!("Failed to serialize custom applications: {}", e))?;
+
+    std::fs::write(&path, json_content).map_err(|e| format!("Failed to write custom_apps.json: {}", e))
+}
+
+/// Add an application to `AppState`, replacing any copy live sync already added
+fn insert_into_state(app_handle: &AppHandle, app: &GHUBApp) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut apps = state
+        .applications
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+    match apps.iter_mut().find(|a| a.application_id == app.application_id) {
+        Some(existing) => *existing = app.clone(),
+        None => apps.push(app.clone()),
+    }
+    Ok(())
+}
+
+async fn send_to_ghub(ws_client: &Arc<WebSocketClient>, app: &GHUBApp) -> Result<(), String> {
+    let payload = serde_json::to_value(app)
+        .map_err(|e| format!("Failed to serialize application: {}", e))?;
+
+    ws_client
+        .send_message(WebSocketMessage {
+            verb: "SET".to_string(),
+            path: "/application".to_string(),
+            payload,
+        })
+        .await
+        .map_err(|e| format!("Failed to send WebSocket message: {}", e))
+}
+
+/// Ids of the applications the running G HUB knows about
+async fn ghub_application_ids(ws_client: &Arc<WebSocketClient>) -> Result<HashSet<String>, String> {
+    let reply = ws_client
+        .request(WebSocketMessage {
+            verb: "GET".to_string(),
+            path: "/applications".to_string(),
+            payload: json!({}),
+        })
+        .await
+        .map_err(|e| format!("Failed to list G HUB applications: {}", e))?;
+
+    Ok(reply["payload"]["applications"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|app| app["applicationId"].as_str())
+        .map(String::from)
+        .collect())
+}
+
+/// Push custom applications that are no longer known to G HUB back to their targets,
+/// e.g. after an update replaced applications.json. Returns the re-created ids.
+/// G HUB is asked which applications it has, since ones sent to it may never
+/// have been written to applications.json. Re-creations aren't journaled.
+pub async fn recreate_missing_custom_apps(
+    app_handle: &AppHandle,
+    ws_client: &Arc<WebSocketClient>,
+) -> Result<Vec<String>, String> {
+    let custom_apps = load_custom_apps(app_handle)?;
+    let existing = get_stored_applications(app_handle)?;
+    let in_ghub = if custom_apps.applications.iter().any(|r| r.target == CustomAppTarget::GHub) {
+        Some(ghub_application_ids(ws_client).await)
+    } else {
+        None
+    };
+
+    let mut recreated = Vec::new();
+    let mut to_append = Vec::new();
+
+    for record in &custom_apps.applications {
+        let app = &record.application;
+        let result = match record.target {
+            CustomAppTarget::GHub => match &in_ghub {
+                Some(Ok(ids)) if ids.contains(&app.application_id) => continue,
+                Some(Err(e)) => Err(e.clone()),
+                _ => send_to_ghub(ws_client, app).await,
+            },
+            CustomAppTarget::ApplicationsJson => {
+                if existing.iter().any(|a| a.application_id == app.application_id) {
+                    continue;
+                }
+                to_append.push(app.clone());
+                Ok(())
+            }
+        };
+
+        match result.and_then(|_| insert_into_state(app_handle, app)) {
+            Ok(()) => recreated.push(app.application_id.clone()),
+            Err(e) => eprintln!("Warning: Failed to re-create custom application '{}': {}", app.name, e),
+        }
+    }
+
+    if !to_append.is_empty() {
+        append_applications_to_disk(app_handle, &to_append)?;
+    }
+
+    Ok(recreated)
+}
+
+/// Create a new application and write it to G HUB or applications.json
+#[tauri::command]
+pub async fn custom_app_create(
+    app_handle: AppHandle,
+    ws_client: State<'_, Arc<WebSocketClient>>,
+    request: CustomAppRequest,
+    target: CustomAppTarget,
+) -> Result<GHUBApp, String> {
+    let app = build_custom_app(request);
+    let existing = get_stored_applications(&app_handle)?;
+    validate_custom_app(&app, &existing).map_err(|errors| errors.join("; "))?;
+    ensure_valid(&app)?;
+
+    match target {
+        CustomAppTarget::GHub => send_to_ghub(&ws_client, &app).await?,
+        // Only the new entry is written; other unsaved edits stay pending
+        CustomAppTarget::ApplicationsJson => append_applications_to_disk(&app_handle, std::slice::from_ref(&app))?,
+    }
+    insert_into_state(&app_handle, &app)?;
+    // Undo only changes local state, which would leave the application in G HUB
+    if target == CustomAppTarget::ApplicationsJson {
+        let description = format!("Create {}", app.name);
+        record_edit(&app_handle, EditSource::Editor, &description, None, Some(app.clone()))?;
+    }
+
+    let mut custom_apps = load_custom_apps(&app_handle)?;
+    custom_apps.applications.push(CustomAppRecord {
+        application: app.clone(),
+        target,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    });
+    save_custom_apps(&app_handle, &custom_apps)?;
+
+    eprintln!("Created custom application '{}' ({})", app.name, app.application_id);
+    Ok(app)
+}
+
+/// Keep the stored copy of a custom application current after it was edited, so
+/// re-creating it after a G HUB update doesn't bring back an old version
+pub fn sync_custom_app_record(app_handle: &AppHandle, app: &GHUBApp) {
+    let result = load_custom_apps(app_handle).and_then(|mut custom_apps| {
+        let Some(record) = custom_apps
+            .applications
+            .iter_mut()
+            .find(|record| record.application.application_id == app.application_id)
+        else {
+            return Ok(());
+        };
+        if record.application == *app {
+            return Ok(());
+        }
+        record.application = app.clone();
+        save_custom_apps(app_handle, &custom_apps)
+    });
+
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to update custom application '{}': {}", app.name, e);
+    }
+}
+
+#[tauri::command]
+pub async fn custom_app_list(app_handle: AppHandle) -> Result<Vec<CustomAppRecord>, String> {
+    Ok(load_custom_apps(&app_handle)?.applications)
+}
+
+/// Stop tracking a custom application; it is left in G HUB as-is
+#[tauri::command]
+pub async fn custom_app_forget(app_handle: AppHandle, application_id: String) -> Result<(), String> {
+    let mut custom_apps = load_custom_apps(&app_handle)?;
+    let before = custom_apps.applications.len();
+    custom_apps
+        .applications
+        .retain(|record| record.application.application_id != application_id);
+
+    if custom_apps.applications.len() == before {
+        return Err(format!("Custom application '{}' not found", application_id));
+    }
+
+    save_custom_apps(&app_handle, &custom_apps)
+}
+
+#[tauri::command]
+pub async fn custom_app_recreate_missing(
+    app_handle: AppHandle,
+    ws_client: State<'_, Arc<WebSocketClient>>,
+) -> Result<Vec<String>, String> {
+    recreate_missing_custom_apps(&app_handle, &ws_client).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::models::SteamApp;
+
+    fn request(name: &str) -> CustomAppRequest {
+        CustomAppRequest {
+            name: name.to_string(),
+            detection: vec![Detection::Steam {
+                steam: SteamApp { app_id: "440".to_string() },
+            }],
+            poster_url: String::new(),
+            poster_title_position: None,
+            category_colors: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn built_apps_get_unique_ids() {
+        let a = build_custom_app(request(" My Game "));
+        let b = build_custom_app(request("My Game"));
+
+        assert_eq!(a.name, "My Game");
+        assert_ne!(a.application_id, b.application_id);
+        assert!(validate_custom_app(&a, &[]).is_ok());
+    }
+
+    #[test]
+    fn validation_reports_every_problem() {
+        let existing = vec![build_custom_app(request("Taken"))];
+        let mut app = build_custom_app(request("taken"));
+        app.detection.clear();
+        app.category_colors = vec![
+            CategoryColor { hex: "#ff0000".to_string(), tag: "Combat".to_string() },
+            CategoryColor { hex: "#00ff00".to_string(), tag: "Combat".to_string() },
+        ];
+
+        let errors = validate_custom_app(&app, &existing).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("already exists"));
+    }
+}
@@ -0,0 +1,373 @@
+use crate::applications::applications_json::read_applications_json;
+use crate::applications::backups::get_backup_path;
+use crate::applications::builds::read_build_applications;
+use crate::applications::models::GHUBApp;
+use crate::applications::paths::get_build_id;
+use crate::core::state::get_stored_applications;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// One field-level difference. `field` is the JSON name of the field, with the
+/// list item's key appended: `category/name` for commands (e.g. `commands.Move/Jump`)
+/// and the tag for category colors. Repeated keys get `#2`, `#3`, ... in order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub kind: ChangeKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationSummary {
+    pub application_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationDiff {
+    pub application_id: String,
+    /// Name on the new side
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Structural difference between two sets of applications, keyed by `application_id`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationsDiff {
+    pub added: Vec<ApplicationSummary>,
+    pub removed: Vec<ApplicationSummary>,
+    pub changed: Vec<ApplicationDiff>,
+}
+
+impl ApplicationsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn summary(app: &GHUBApp) -> ApplicationSummary {
+    ApplicationSummary {
+        application_id: app.application_id.clone(),
+        name: app.name.clone(),
+    }
+}
+
+/// Compare two application lists
+pub fn diff_applications_detailed(old: &[GHUBApp], new: &[GHUBApp]) -> ApplicationsDiff {
+    let mut diff = ApplicationsDiff::default();
+
+    for app in new {
+        match old.iter().find(|a| a.application_id == app.application_id) {
+            None => diff.added.push(summary(app)),
+            Some(existing) => {
+                let changes = diff_application(existing, app);
+                if !changes.is_empty() {
+                    diff.changed.push(ApplicationDiff {
+                        application_id: app.application_id.clone(),
+                        name: app.name.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+
+    for app in old {
+        if !new.iter().any(|a| a.application_id == app.application_id) {
+            diff.removed.push(summary(app));
+        }
+    }
+
+    diff
+}
+
+/// Field-level changes between two versions of the same application
+pub fn diff_application(old: &GHUBApp, new: &GHUBApp) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    let scalars = [
+        ("name", Value::from(old.name.as_str()), Value::from(new.name.as_str())),
+        (
+            "posterTitlePosition",
+            Value::from(old.poster_title_position.as_str()),
+            Value::from(new.poster_title_position.as_str()),
+        ),
+        ("posterUrl", Value::from(old.poster_url.as_str()), Value::from(new.poster_url.as_str())),
+        ("version", Value::from(old.version), Value::from(new.version)),
+    ];
+    for (field, before, after) in scalars {
+        if before != after {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                kind: ChangeKind::Modified,
+                before: Some(before),
+                after: Some(after),
+            });
+        }
+    }
+
+    // Detections have no identity of their own, so compare them as a set
+    let old_detections = to_values(&old.detection);
+    let new_detections = to_values(&new.detection);
+    for detection in &old_detections {
+        if !new_detections.contains(detection) {
+            changes.push(list_change("detection", ChangeKind::Removed, Some(detection), None));
+        }
+    }
+    for detection in &new_detections {
+        if !old_detections.contains(detection) {
+            changes.push(list_change("detection", ChangeKind::Added, None, Some(detection)));
+        }
+    }
+
+    diff_keyed(
+        &mut changes,
+        "commands",
+        &keyed(&old.commands, |c| format!("{}/{}", c.category, c.name)),
+        &keyed(&new.commands, |c| format!("{}/{}", c.category, c.name)),
+    );
+    diff_keyed(
+        &mut changes,
+        "categoryColors",
+        &keyed(&old.category_colors, |c| c.tag.clone()),
+        &keyed(&new.category_colors, |c| c.tag.clone()),
+    );
+    diff_keyed(
+        &mut changes,
+        "extra",
+        &old.extra.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<Vec<_>>(),
+        &new.extra.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<Vec<_>>(),
+    );
+
+    changes
+}
+
+fn to_values<T: Serialize>(items: &[T]) -> Vec<Value> {
+    items
+        .iter()
+        .filter_map(|item| serde_json::to_value(item).ok())
+        .collect()
+}
+
+/// Pair items with their key, numbering repeats so every key is unique
+fn keyed<T: Serialize>(items: &[T], key: impl Fn(&T) -> String) -> Vec<(String, Value)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    items
+        .iter()
+        .filter_map(|item| {
+            let value = serde_json::to_value(item).ok()?;
+            let key = key(item);
+            let count = seen.entry(key.clone()).or_insert(0);
+            *count += 1;
+            match *count {
+                1 => Some((key, value)),
+                n => Some((format!("{}#{}", key, n), value)),
+            }
+        })
+        .collect()
+}
+
+fn list_change(field: &str, kind: ChangeKind, before: Option<&Value>, after: Option<&Value>) -> FieldChange {
+    FieldChange {
+        field: field.to_string(),
+        kind,
+        before: before.cloned(),
+        after: after.cloned(),
+    }
+}
+
+/// Compare list items identified by a key, reporting each under `field.key`
+fn diff_keyed(changes: &mut Vec<FieldChange>, field: &str, old: &[(String, Value)], new: &[(String, Value)]) {
+    let keys: BTreeSet<&String> = old.iter().chain(new.iter()).map(|(k, _)| k).collect();
+    let find = |items: &'_ [(String, Value)], key: &String| {
+        items.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    };
+
+    for key in keys {
+        let name = format!("{}.{}", field, key);
+        let (kind, before, after) = match (find(old, key), find(new, key)) {
+            (None, Some(after)) => (ChangeKind::Added, None, Some(after)),
+            (Some(before), None) => (ChangeKind::Removed, Some(before), None),
+            (Some(before), Some(after)) if before != after => (ChangeKind::Modified, Some(before), Some(after)),
+            _ => continue,
+        };
+        changes.push(FieldChange {
+            field: name,
+            kind,
+            before,
+            after,
+        });
+    }
+}
+
+/// Applications in a G HUB build's applications.json
+pub fn build_applications(app_handle: &AppHandle, build_id: &str) -> Result<Vec<GHUBApp>, String> {
+    Ok(read_build_applications(app_handle, build_id)?.applications)
+}
+
+/// Applications in a backup
+pub fn backup_applications(app_handle: &AppHandle, backup_id: &str) -> Result<Vec<GHUBApp>, String> {
+    Ok(read_applications_json(&get_backup_path(app_handle, backup_id)?)?.applications)
+}
+
+/// Compare applications.json of two G HUB builds
+#[tauri::command]
+pub async fn diff_builds(
+    app_handle: AppHandle,
+    old_build_id: String,
+    new_build_id: String,
+) -> Result<ApplicationsDiff, String> {
+    let old = build_applications(&app_handle, &old_build_id)?;
+    let new = build_applications(&app_handle, &new_build_id)?;
+    Ok(diff_applications_detailed(&old, &new))
+}
+
+/// Compare two backups
+#[tauri::command]
+pub async fn diff_backups(
+    app_handle: AppHandle,
+    old_backup_id: String,
+    new_backup_id: String,
+) -> Result<ApplicationsDiff, String> {
+    let old = backup_applications(&app_handle, &old_backup_id)?;
+    let new = backup_applications(&app_handle, &new_backup_id)?;
+    Ok(diff_applications_detailed(&old, &new))
+}
+
+/// Field-level comparison of applications.json on disk with the unsaved
+/// applications in memory. `get_pending_changes` only lists the changed ids.
+#[tauri::command]
+pub async fn diff_disk_with_memory(app_handle: AppHandle) -> Result<ApplicationsDiff, String> {
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+    let on_disk = build_applications(&app_handle, &build_id)?;
+    let in_memory = get_stored_applications(&app_handle)?;
+    Ok(diff_applications_detailed(&on_disk, &in_memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn app(value: Value) -> GHUBApp {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_applications() {
+        let old = vec![
+            app(json!({ "applicationId": "a", "name": "A" })),
+            app(json!({ "applicationId": "b", "name": "B" })),
+        ];
+        let new = vec![
+            app(json!({ "applicationId": "a", "name": "A renamed" })),
+            app(json!({ "applicationId": "c", "name": "C" })),
+        ];
+
+        let diff = diff_applications_detailed(&old, &new);
+
+        assert_eq!(diff.added[0].application_id, "c");
+        assert_eq!(diff.removed[0].name, "B");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].changes,
+            vec![FieldChange {
+                field: "name".to_string(),
+                kind: ChangeKind::Modified,
+                before: Some(json!("A")),
+                after: Some(json!("A renamed")),
+            }]
+        );
+    }
+
+    #[test]
+    fn detections_commands_and_colors_are_compared_per_item() {
+        let old = app(json!({
+            "applicationId": "a",
+            "name": "A",
+            "posterUrl": "file://old.jpg",
+            "detection": [{ "steam": { "appId": "1" } }, { "glob": "C:/A/a.exe" }],
+            "commands": [
+                { "category": "Move", "keystroke": ["W"], "name": "Forward" },
+                { "category": "Move", "keystroke": ["SPACE"], "name": "Jump" }
+            ],
+            "categoryColors": [{ "hex": "#ff0000", "tag": "Move" }]
+        }));
+        let new = app(json!({
+            "applicationId": "a",
+            "name": "A",
+            "posterUrl": "file://new.jpg",
+            "detection": [{ "glob": "C:/A/a.exe" }, { "epicGames": { "appName": "A" } }],
+            "commands": [
+                { "category": "Move", "keystroke": ["UP"], "name": "Forward" }
+            ],
+            "categoryColors": [{ "hex": "#ff0000", "tag": "Move" }]
+        }));
+
+        let changes = diff_application(&old, &new);
+        let summary: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.field.as_str(), c.kind)).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("posterUrl", ChangeKind::Modified),
+                ("detection", ChangeKind::Removed),
+                ("detection", ChangeKind::Added),
+                ("commands.Move/Forward", ChangeKind::Modified),
+                ("commands.Move/Jump", ChangeKind::Removed),
+            ]
+        );
+        assert_eq!(changes[1].before, Some(json!({ "steam": { "appId": "1" } })));
+    }
+
+    #[test]
+    fn identical_lists_produce_an_empty_diff() {
+        let apps = vec![app(json!({ "applicationId": "a", "name": "A", "databaseId": "x" }))];
+        assert!(diff_applications_detailed(&apps, &apps).is_empty());
+    }
+
+    #[test]
+    fn commands_are_keyed_by_category_and_name_and_repeats_are_numbered() {
+        let old = app(json!({
+            "applicationId": "a",
+            "name": "A",
+            "commands": [
+                { "category": "Move", "keystroke": ["W"], "name": "Use" },
+                { "category": "Items", "keystroke": ["E"], "name": "Use" },
+                { "category": "Items", "keystroke": ["1"], "name": "Slot" },
+                { "category": "Items", "keystroke": ["2"], "name": "Slot" }
+            ]
+        }));
+        let new = app(json!({
+            "applicationId": "a",
+            "name": "A",
+            "commands": [
+                { "category": "Move", "keystroke": ["W"], "name": "Use" },
+                { "category": "Items", "keystroke": ["F"], "name": "Use" },
+                { "category": "Items", "keystroke": ["1"], "name": "Slot" },
+                { "category": "Items", "keystroke": ["3"], "name": "Slot" }
+            ]
+        }));
+
+        let changes = diff_application(&old, &new);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+
+        assert_eq!(fields, vec!["commands.Items/Slot#2", "commands.Items/Use"]);
+        assert!(changes.iter().all(|c| c.kind == ChangeKind::Modified));
+    }
+}
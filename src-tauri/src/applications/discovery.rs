@@ -0,0 +1,151 @@
+use crate::applications::ghub_version::read_version_json;
+use crate::applications::validation::{inspect_data_path, PathDiagnostics};
+use crate::core::constants::{LGHUB_DATA_PATH_ENV, LGHUB_DEFAULT_DATA_PATH, STORE_KEY_DATA_PATH};
+use crate::core::store::get_store_key;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CandidateSource {
+    /// The path currently saved in settings
+    Configured,
+    /// Set through the `LGHUB_DATA_PATH` environment variable
+    Environment,
+    /// A location G HUB installs to on this platform
+    KnownLocation,
+}
+
+/// A directory that may be the G HUB data directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPathCandidate {
+    pub path: PathBuf,
+    pub source: CandidateSource,
+    pub diagnostics: PathDiagnostics,
+    pub version: Option<String>,
+    pub is_valid: bool,
+}
+
+impl DataPathCandidate {
+    pub fn inspect(path: PathBuf, source: CandidateSource) -> Self {
+        let diagnostics = inspect_data_path(&path);
+        let version = read_version_json(&path.join("version.json")).ok().map(|v| v.version);
+
+        DataPathCandidate {
+            is_valid: diagnostics.is_valid,
+            path,
+            source,
+            diagnostics,
+            version,
+        }
+    }
+
+    /// How complete the directory looks; higher is better
+    fn score(&self) -> usize {
+        self.diagnostics.checks.iter().filter(|c| c.ok).count()
+    }
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// Where G HUB keeps its data on this platform
+pub fn known_data_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if cfg!(target_os = "windows") {
+        if let Some(program_data) = env_path("PROGRAMDATA") {
+            paths.push(program_data.join("LGHUB"));
+        }
+        paths.push(PathBuf::from(LGHUB_DEFAULT_DATA_PATH));
+    } else if cfg!(target_os = "macos") {
+        paths.push(PathBuf::from("/Users/Shared/LGHUB"));
+        if let Some(home) = env_path("HOME") {
+            paths.push(home.join("Library/Application Support/LGHUB"));
+        }
+    } else if let Some(home) = env_path("HOME") {
+        // G HUB running under Wine
+        paths.push(home.join(".wine/drive_c/ProgramData/LGHUB"));
+    }
+
+    paths
+}
+
+/// Inspect every candidate location, best first. Duplicate paths keep their first source.
+pub fn discover_data_paths(configured: Option<&Path>) -> Vec<DataPathCandidate> {
+    let sources = configured
+        .map(|p| (p.to_path_buf(), CandidateSource::Configured))
+        .into_iter()
+        .chain(env_path(LGHUB_DATA_PATH_ENV).map(|p| (p, CandidateSource::Environment)))
+        .chain(known_data_paths().into_iter().map(|p| (p, CandidateSource::KnownLocation)));
+
+    let mut candidates: Vec<DataPathCandidate> = Vec::new();
+    for (path, source) in sources {
+        if !candidates.iter().any(|c| c.path == path) {
+            candidates.push(DataPathCandidate::inspect(path, source));
+        }
+    }
+
+    // Stable sort keeps configured and environment paths ahead on ties
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score()));
+    candidates
+}
+
+/// The best valid candidate, if any
+pub fn best_data_path(candidates: &[DataPathCandidate]) -> Option<&DataPathCandidate> {
+    candidates.iter().find(|c| c.is_valid)
+}
+
+/// Probe known G HUB data locations and report each with its build id
+#[tauri::command]
+pub async fn discover_data_path_candidates(app_handle: AppHandle) -> Vec<DataPathCandidate> {
+    let configured = get_store_key(&app_handle, STORE_KEY_DATA_PATH)
+        .and_then(|v| v.as_str().map(PathBuf::from));
+    discover_data_paths(configured.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fake_data_dir(name: &str, complete: bool) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("g-hauler-discovery-{}-{}", name, std::process::id()));
+        let data = dir.join("depots/1234/core/LGHUB/data");
+        fs::create_dir_all(&data).unwrap();
+        fs::write(dir.join("current.json"), r#"{ "buildId": "1234" }"#).unwrap();
+        if complete {
+            fs::create_dir_all(dir.join("depots/1234/core_apps/images")).unwrap();
+            fs::write(dir.join("version.json"), r#"{ "version": "2025.3.1" }"#).unwrap();
+            fs::write(data.join("applications.json"), r#"{ "applications": [] }"#).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn valid_directories_rank_first() {
+        let partial = fake_data_dir("partial", false);
+        let complete = fake_data_dir("complete", true);
+
+        std::env::set_var(LGHUB_DATA_PATH_ENV, &complete);
+        let candidates = discover_data_paths(Some(&partial));
+        std::env::remove_var(LGHUB_DATA_PATH_ENV);
+
+        let best = best_data_path(&candidates).unwrap();
+        assert_eq!(best.path, complete);
+        assert_eq!(best.source, CandidateSource::Environment);
+        assert_eq!(best.diagnostics.build_id.as_deref(), Some("1234"));
+        assert_eq!(best.version.as_deref(), Some("2025.3.1"));
+        assert!(std::ptr::eq(best, &candidates[0]));
+
+        let configured = candidates.iter().find(|c| c.path == partial).unwrap();
+        assert_eq!(configured.source, CandidateSource::Configured);
+        assert!(!configured.is_valid);
+
+        fs::remove_dir_all(partial).unwrap();
+        fs::remove_dir_all(complete).unwrap();
+    }
+}
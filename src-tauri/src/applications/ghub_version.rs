@@ -1,53 +1,342 @@
-use crate::applications::paths::get_version_json_path;
-use crate::core::constants::STORE_KEY_LAST_GHUB_VERSION;
+use crate::applications::custom_apps::recreate_missing_custom_apps;
+use crate::applications::models::{CurrentJson, VersionJson};
+use crate::applications::paths::{get_current_json_path, get_version_json_path};
+use crate::applications::posters::reapply_poster_overrides;
+use crate::core::constants::{STORE_KEY_LAST_GHUB_CHANNEL, STORE_KEY_LAST_GHUB_VERSION};
 use crate::core::store::{get_store_key, set_store_key};
+use crate::ghub_game_patches::applier::reapply_saved_patches;
+use crate::websocket::client::WebSocketClient;
+use serde::Serialize;
+use std::cmp::Ordering;
 use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 use tauri::AppHandle;
 
+/// A dotted G HUB version such as `2025.3.682153`, with an optional
+/// `-suffix` that sorts before the plain release. Missing parts count as 0.
+#[derive(Debug, Clone)]
+pub struct GHubVersion {
+    parts: Vec<u64>,
+    pre_release: Option<String>,
+}
+
+impl GHubVersion {
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim().trim_start_matches(['v', 'V']);
+        let version = version.split('+').next().unwrap_or_default();
+        let (numbers, pre_release) = match version.split_once('-') {
+            Some((numbers, pre)) => (numbers, Some(pre.to_string())),
+            None => (version, None),
+        };
+
+        let parts = numbers
+            .split('.')
+            .map(|p| p.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(GHubVersion { parts, pre_release })
+    }
+}
+
+impl Ord for GHubVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.parts.len().max(other.parts.len());
+        let part = |v: &GHubVersion, i: usize| v.parts.get(i).copied().unwrap_or(0);
+
+        (0..len)
+            .map(|i| part(self, i).cmp(&part(other, i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialEq for GHubVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for GHubVersion {}
+
+impl PartialOrd for GHubVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// How the installed G HUB differs from the one seen on the last run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum VersionChange {
+    Unchanged,
+    /// No version was recorded yet
+    FirstRun,
+    Upgrade { from: String, to: String },
+    Downgrade { from: String, to: String },
+    /// Moved between release channels, e.g. stable to beta
+    ChannelSwitch { from: String, to: String },
+    /// The version string changed but couldn't be compared
+    Changed { from: String, to: String },
+}
+
+impl VersionChange {
+    /// Whether G HUB may have replaced its data files
+    pub fn is_changed(&self) -> bool {
+        *self != VersionChange::Unchanged
+    }
+}
+
+/// Compare the stored version and channel with the installed ones
+pub fn compare_versions(
+    stored_version: Option<&str>,
+    stored_channel: Option<&str>,
+    current_version: &str,
+    current_channel: Option<&str>,
+) -> VersionChange {
+    let Some(stored_version) = stored_version.filter(|v| !v.is_empty()) else {
+        return VersionChange::FirstRun;
+    };
+
+    if let (Some(from), Some(to)) = (stored_channel.filter(|c| !c.is_empty()), current_channel) {
+        if !from.eq_ignore_ascii_case(to) {
+            return VersionChange::ChannelSwitch {
+                from: from.to_string(),
+                to: to.to_string(),
+            };
+        }
+    }
+
+    let from = stored_version.to_string();
+    let to = current_version.to_string();
+    match (GHubVersion::parse(stored_version), GHubVersion::parse(current_version)) {
+        (Some(old), Some(new)) => match new.cmp(&old) {
+            Ordering::Greater => VersionChange::Upgrade { from, to },
+            Ordering::Less => VersionChange::Downgrade { from, to },
+            Ordering::Equal => VersionChange::Unchanged,
+        },
+        _ if from == to => VersionChange::Unchanged,
+        _ => VersionChange::Changed { from, to },
+    }
+}
+
+pub fn read_current_json(path: &Path) -> Result<CurrentJson, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read current.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse current.json: {}", e))
+}
+
+pub fn read_version_json(path: &Path) -> Result<VersionJson, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read version.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse version.json: {}", e))
+}
+
+pub fn get_version_json(app_handle: &AppHandle) -> Option<VersionJson> {
+    read_version_json(&get_version_json_path(app_handle)?).ok()
+}
+
 /// Get the current G HUB version from version.json
 pub fn get_ghub_version(app_handle: &AppHandle) -> Option<String> {
-    let version_json_path = get_version_json_path(app_handle)?;
-    let content = fs::read_to_string(version_json_path).ok()?;
-    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
-    json.get("version")?.as_str().map(|s| s.to_string())
+    get_version_json(app_handle).map(|v| v.version)
+}
+
+/// The release channel, from version.json or current.json
+fn current_channel(app_handle: &AppHandle, version_json: &VersionJson) -> Option<String> {
+    version_json.channel.clone().or_else(|| {
+        let current = read_current_json(&get_current_json_path(app_handle)?).ok()?;
+        current.channel
+    })
 }
 
-/// Check if G HUB version has changed since last run
-pub fn has_version_changed(app_handle: &AppHandle) -> Result<bool, String> {
-    // Get current G HUB version from version.json
-    let current_version = get_ghub_version(app_handle);
+/// Check how G HUB's version has changed since the last run
+pub fn has_version_changed(app_handle: &AppHandle) -> Result<VersionChange, String> {
+    let version_json = get_version_json(app_handle)
+        .ok_or("Could not determine G HUB version")?;
+    let channel = current_channel(app_handle, &version_json);
 
-    // Get last stored version
     let stored_version = get_store_key(app_handle, STORE_KEY_LAST_GHUB_VERSION)
         .and_then(|v| v.as_str().map(String::from));
+    let stored_channel = get_store_key(app_handle, STORE_KEY_LAST_GHUB_CHANNEL)
+        .and_then(|v| v.as_str().map(String::from));
 
-    match (current_version, stored_version) {
-        (Some(current), Some(stored)) => {
-            // Both versions exist - compare them
-            Ok(current != stored)
-        }
-        (Some(_), None) => {
-            // Current version exists but no stored version - treat as changed
-            Ok(true)
-        }
-        (None, _) => {
-            // Can't determine current version - don't reapply patches
-            Err("Could not determine G HUB version".to_string())
-        }
-    }
+    Ok(compare_versions(
+        stored_version.as_deref(),
+        stored_channel.as_deref(),
+        &version_json.version,
+        channel.as_deref(),
+    ))
 }
 
-/// Update the stored G HUB version to the current version
+/// Update the stored G HUB version and channel to the current ones
 pub fn update_stored_version(app_handle: &AppHandle) -> Result<(), String> {
-    let current_version = get_ghub_version(app_handle)
+    let version_json = get_version_json(app_handle)
         .ok_or("Could not determine G HUB version")?;
+    let channel = current_channel(app_handle, &version_json);
 
     set_store_key(
         app_handle,
         STORE_KEY_LAST_GHUB_VERSION,
-        serde_json::json!(current_version),
+        serde_json::json!(version_json.version),
+    )?;
+    set_store_key(
+        app_handle,
+        STORE_KEY_LAST_GHUB_CHANNEL,
+        serde_json::json!(channel.unwrap_or_default()),
     )?;
 
-    println!("Updated stored G HUB version to: {}", current_version);
+    eprintln!("Updated stored G HUB version to: {}", version_json.version);
     Ok(())
 }
+
+/// What `restore_after_version_change` did
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionChangeRestore {
+    pub change: VersionChange,
+    pub recreated_custom_apps: Vec<String>,
+    pub restored_posters: Vec<String>,
+    pub patches_reapplied: bool,
+    /// Steps that failed; the stored version is only updated when patches were reapplied
+    pub warnings: Vec<String>,
+}
+
+/// After a G HUB update, re-create custom applications, restore replaced
+/// posters and reapply saved detection patches, then remember the version so
+/// this runs once per update. With `force`, saved patches are reapplied even
+/// when the version is unchanged.
+pub async fn restore_after_version_change(
+    app_handle: &AppHandle,
+    ws_client: &Arc<WebSocketClient>,
+    force: bool,
+) -> Result<VersionChangeRestore, String> {
+    let change = has_version_changed(app_handle)?;
+    let mut restore = VersionChangeRestore {
+        change,
+        recreated_custom_apps: Vec::new(),
+        restored_posters: Vec::new(),
+        patches_reapplied: false,
+        warnings: Vec::new(),
+    };
+    let changed = restore.change.is_changed();
+    if !changed && !force {
+        return Ok(restore);
+    }
+
+    if changed {
+        match recreate_missing_custom_apps(app_handle, ws_client).await {
+            Ok(recreated) => restore.recreated_custom_apps = recreated,
+            Err(e) => restore.warnings.push(format!("Failed to re-create custom applications: {}", e)),
+        }
+        match reapply_poster_overrides(app_handle).await {
+            Ok(restored) => restore.restored_posters = restored,
+            Err(e) => restore.warnings.push(format!("Failed to restore replaced posters: {}", e)),
+        }
+    }
+
+    match reapply_saved_patches(app_handle, ws_client).await {
+        Ok(()) => {
+            restore.patches_reapplied = true;
+            if changed {
+                if let Err(e) = update_stored_version(app_handle) {
+                    restore.warnings.push(format!("Failed to update stored G HUB version: {}", e));
+                }
+            }
+        }
+        Err(e) => restore.warnings.push(format!("Failed to reapply saved detection patches: {}", e)),
+    }
+
+    Ok(restore)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GHubVersionInfo {
+    pub current: Option<CurrentJson>,
+    pub version: Option<VersionJson>,
+    /// Version seen on the last run
+    pub stored_version: Option<String>,
+    pub change: Option<VersionChange>,
+    /// Why current.json or version.json couldn't be read
+    pub errors: Vec<String>,
+}
+
+/// Parsed current.json and version.json, and how they compare with the last run
+#[tauri::command]
+pub async fn get_ghub_version_info(app_handle: AppHandle) -> GHubVersionInfo {
+    let mut errors = Vec::new();
+
+    let current = match get_current_json_path(&app_handle) {
+        Some(path) => read_current_json(&path).map_err(|e| errors.push(e)).ok(),
+        None => None,
+    };
+    let version = match get_version_json_path(&app_handle) {
+        Some(path) => read_version_json(&path).map_err(|e| errors.push(e)).ok(),
+        None => {
+            errors.push("G HUB data path is not set".to_string());
+            None
+        }
+    };
+
+    GHubVersionInfo {
+        current,
+        version,
+        stored_version: get_store_key(&app_handle, STORE_KEY_LAST_GHUB_VERSION)
+            .and_then(|v| v.as_str().map(String::from)),
+        change: has_version_changed(&app_handle).ok(),
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically() {
+        let v = |s: &str| GHubVersion::parse(s).unwrap();
+        assert!(v("2025.10.1") > v("2025.9.999"));
+        assert!(v("2025.3") == v("2025.3.0"));
+        assert!(v("2025.3.1-beta") < v("2025.3.1"));
+        assert!(GHubVersion::parse("latest").is_none());
+    }
+
+    #[test]
+    fn distinguishes_upgrades_downgrades_and_channel_switches() {
+        assert_eq!(compare_versions(Some(""), None, "2025.3.1", None), VersionChange::FirstRun);
+        assert_eq!(
+            compare_versions(Some("2025.3.1"), Some("stable"), "2025.3.1", Some("stable")),
+            VersionChange::Unchanged
+        );
+        assert!(matches!(
+            compare_versions(Some("2025.3.1"), None, "2025.4.0", None),
+            VersionChange::Upgrade { .. }
+        ));
+        assert!(matches!(
+            compare_versions(Some("2025.4.0"), None, "2025.3.1", None),
+            VersionChange::Downgrade { .. }
+        ));
+        assert_eq!(
+            compare_versions(Some("2025.3.1"), Some("stable"), "2025.4.0-beta", Some("beta")),
+            VersionChange::ChannelSwitch {
+                from: "stable".to_string(),
+                to: "beta".to_string()
+            }
+        );
+        assert!(compare_versions(Some("a"), None, "b", None).is_changed());
+    }
+
+    #[test]
+    fn version_files_keep_unknown_fields() {
+        let version: VersionJson =
+            serde_json::from_str(r#"{ "version": "2025.3.1", "channel": "stable", "sha": "abc" }"#).unwrap();
+        assert_eq!(version.channel.as_deref(), Some("stable"));
+        assert_eq!(version.extra["sha"], "abc");
+
+        let current: CurrentJson = serde_json::from_str(r#"{ "buildId": "1234" }"#).unwrap();
+        assert_eq!(current.build_id, "1234");
+    }
+}
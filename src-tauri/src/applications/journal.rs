@@ -0,0 +1,510 @@
+use crate::applications::applications_json::{load_and_store_applications, read_applications_json};
+use crate::applications::live_sync::{diff_applications, ApplicationsChanged};
+use crate::applications::models::GHUBApp;
+use crate::applications::paths::{get_applications_json_path, get_build_id};
+use crate::core::constants::EDIT_JOURNAL_CAPACITY;
+use crate::core::state::AppState;
+use crate::ghub_game_patches::applier::send_application;
+use crate::websocket::client::WebSocketClient;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+/// What made an edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EditSource {
+    Editor,
+    PatchApplier,
+    Import,
+}
+
+/// One application's change within an entry. `before`/`after` are `None` when
+/// the application didn't exist on that side of the edit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationEdit {
+    pub application_id: String,
+    pub before: Option<GHUBApp>,
+    pub after: Option<GHUBApp>,
+}
+
+/// A user-level operation, undone and redone as a whole. Most change a single
+/// application; bulk edits change many.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub id: u64,
+    pub timestamp: String,
+    pub source: EditSource,
+    pub description: String,
+    pub edits: Vec<ApplicationEdit>,
+}
+
+impl JournalEntry {
+    fn application_ids(&self) -> impl Iterator<Item = &str> {
+        self.edits.iter().map(|edit| edit.application_id.as_str())
+    }
+}
+
+/// Summary of the journal for the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalState {
+    pub can_undo: bool,
+    pub can_redo: bool,
+    /// Oldest first
+    pub undo_stack: Vec<JournalEntry>,
+    /// Next entry to redo last
+    pub redo_stack: Vec<JournalEntry>,
+}
+
+/// Per-session undo/redo history of changes to `AppState::applications`
+#[derive(Debug)]
+pub struct EditJournal {
+    undo_stack: Vec<JournalEntry>,
+    redo_stack: Vec<JournalEntry>,
+    capacity: usize,
+    next_id: u64,
+    /// Applications changed, undone or redone since applications.json was
+    /// last loaded or saved
+    unsaved: HashSet<String>,
+}
+
+impl Default for EditJournal {
+    fn default() -> Self {
+        Self::new(EDIT_JOURNAL_CAPACITY)
+    }
+}
+
+impl EditJournal {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            capacity,
+            next_id: 0,
+            unsaved: HashSet::new(),
+        }
+    }
+
+    /// Record an edit, dropping the redo history. Edits that changed nothing are ignored.
+    pub fn record(
+        &mut self,
+        source: EditSource,
+        description: &str,
+        before: Option<GHUBApp>,
+        after: Option<GHUBApp>,
+    ) {
+        self.record_all(source, description, vec![(before, after)]);
+    }
+
+    /// Record `(before, after)` pairs of one operation as a single entry
+    pub fn record_all(
+        &mut self,
+        source: EditSource,
+        description: &str,
+        changes: Vec<(Option<GHUBApp>, Option<GHUBApp>)>,
+    ) {
+        let edits: Vec<ApplicationEdit> = changes
+            .into_iter()
+            .filter(|(before, after)| before != after)
+            .filter_map(|(before, after)| {
+                let application_id = after.as_ref().or(before.as_ref())?.application_id.clone();
+                Some(ApplicationEdit {
+                    application_id,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+        if edits.is_empty() {
+            return;
+        }
+
+        if self.undo_stack.len() == self.capacity {
+            self.undo_stack.remove(0);
+        }
+        self.unsaved.extend(edits.iter().map(|edit| edit.application_id.clone()));
+        self.undo_stack.push(JournalEntry {
+            id: self.next_id,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            source,
+            description: description.to_string(),
+            edits,
+        });
+        self.next_id += 1;
+        self.redo_stack.clear();
+    }
+
+    /// Revert the most recent entry in `apps`
+    pub fn undo(&mut self, apps: &mut Vec<GHUBApp>) -> Option<JournalEntry> {
+        let entry = self.undo_stack.pop()?;
+        for edit in entry.edits.iter().rev() {
+            set_application(apps, &edit.application_id, edit.before.as_ref());
+        }
+        self.unsaved.extend(entry.application_ids().map(String::from));
+        self.redo_stack.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Re-apply the most recently undone entry in `apps`
+    pub fn redo(&mut self, apps: &mut Vec<GHUBApp>) -> Option<JournalEntry> {
+        let entry = self.redo_stack.pop()?;
+        for edit in &entry.edits {
+            set_application(apps, &edit.application_id, edit.after.as_ref());
+        }
+        self.unsaved.extend(entry.application_ids().map(String::from));
+        self.undo_stack.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Applications with local changes that aren't in applications.json yet.
+    /// Undoing back to the saved state still counts as a change.
+    pub fn pending_ids(&self) -> HashSet<String> {
+        self.unsaved.clone()
+    }
+
+    /// Everything up to now is in applications.json; the history stays undoable
+    pub fn mark_saved(&mut self) {
+        self.unsaved.clear();
+    }
+
+    /// Drop every change to these applications, e.g. after G HUB replaced them
+    pub fn forget(&mut self, application_ids: &HashSet<&str>) {
+        for stack in [&mut self.undo_stack, &mut self.redo_stack] {
+            for entry in stack.iter_mut() {
+                entry.edits.retain(|edit| !application_ids.contains(edit.application_id.as_str()));
+            }
+            stack.retain(|entry| !entry.edits.is_empty());
+        }
+        self.unsaved.retain(|id| !application_ids.contains(id.as_str()));
+    }
+
+    /// The entry `undo` (or `redo`) would apply next
+    pub fn peek(&self, redo: bool) -> Option<&JournalEntry> {
+        if redo {
+            self.redo_stack.last()
+        } else {
+            self.undo_stack.last()
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.unsaved.clear();
+    }
+
+    pub fn state(&self) -> JournalState {
+        JournalState {
+            can_undo: !self.undo_stack.is_empty(),
+            can_redo: !self.redo_stack.is_empty(),
+            undo_stack: self.undo_stack.clone(),
+            redo_stack: self.redo_stack.clone(),
+        }
+    }
+}
+
+/// Replace, insert or remove an application so it matches `snapshot`
+fn set_application(apps: &mut Vec<GHUBApp>, application_id: &str, snapshot: Option<&GHUBApp>) {
+    let position = apps.iter().position(|a| a.application_id == application_id);
+    match (position, snapshot) {
+        (Some(index), Some(app)) => apps[index] = app.clone(),
+        (Some(index), None) => {
+            apps.remove(index);
+        }
+        (None, Some(app)) => apps.push(app.clone()),
+        (None, None) => {}
+    }
+}
+
+/// Record an edit in the journal held in `AppState`
+pub fn record_edit<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    source: EditSource,
+    description: &str,
+    before: Option<GHUBApp>,
+    after: Option<GHUBApp>,
+) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+    journal.record(source, description, before, after);
+    Ok(())
+}
+
+/// Record the `(before, after)` pairs of one operation as a single undo step
+pub fn record_edits<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    source: EditSource,
+    description: &str,
+    changes: Vec<(Option<GHUBApp>, Option<GHUBApp>)>,
+) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+    journal.record_all(source, description, changes);
+    Ok(())
+}
+
+pub fn clear_journal<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+    journal.clear();
+    Ok(())
+}
+
+/// Call after writing the in-memory applications to applications.json
+pub fn mark_journal_saved<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+    journal.mark_saved();
+    Ok(())
+}
+
+fn undo_or_redo<R: Runtime>(app_handle: &AppHandle<R>, redo: bool) -> Result<Option<JournalEntry>, String> {
+    let state: State<AppState> = app_handle.state();
+    // Always lock applications before the journal
+    let mut apps = state
+        .applications
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+    let mut journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+
+    let entry = if redo {
+        journal.redo(&mut apps)
+    } else {
+        journal.undo(&mut apps)
+    };
+
+    if let Some(entry) = &entry {
+        let mut changes = ApplicationsChanged::default();
+        for edit in &entry.edits {
+            let (from, to) = if redo {
+                (&edit.before, &edit.after)
+            } else {
+                (&edit.after, &edit.before)
+            };
+            let list = match (from, to) {
+                (None, Some(_)) => &mut changes.added,
+                (Some(_), None) => &mut changes.removed,
+                _ => &mut changes.modified,
+            };
+            list.push(edit.application_id.clone());
+        }
+        let _ = app_handle.emit("applications-changed", &changes);
+    }
+
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn journal_get_state(app_handle: AppHandle) -> Result<JournalState, String> {
+    let state: State<AppState> = app_handle.state();
+    let journal = state
+        .edit_journal
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+    Ok(journal.state())
+}
+
+/// Patches were already sent to G HUB, so undoing or redoing one sends the
+/// resulting application too. Nothing changes locally if that fails.
+async fn sync_patch_entry(app_handle: &AppHandle, redo: bool) -> Result<(), String> {
+    let entry = {
+        let state: State<AppState> = app_handle.state();
+        let journal = state
+            .edit_journal
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on edit journal: {}", e))?;
+        match journal.peek(redo) {
+            Some(entry) if entry.source == EditSource::PatchApplier => entry.clone(),
+            _ => return Ok(()),
+        }
+    };
+
+    let ws_client = app_handle.state::<Arc<WebSocketClient>>().inner().clone();
+    for edit in &entry.edits {
+        let target = if redo { &edit.after } else { &edit.before };
+        if let Some(app) = target {
+            send_application(&ws_client, app).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Undo the last application edit; returns the undone entry, if any
+#[tauri::command]
+pub async fn journal_undo(app_handle: AppHandle) -> Result<Option<JournalEntry>, String> {
+    sync_patch_entry(&app_handle, false).await?;
+    undo_or_redo(&app_handle, false)
+}
+
+/// Redo the last undone application edit; returns the redone entry, if any
+#[tauri::command]
+pub async fn journal_redo(app_handle: AppHandle) -> Result<Option<JournalEntry>, String> {
+    sync_patch_entry(&app_handle, true).await?;
+    undo_or_redo(&app_handle, true)
+}
+
+/// Applications that differ between applications.json on disk and memory
+#[tauri::command]
+pub async fn get_pending_changes(app_handle: AppHandle) -> Result<ApplicationsChanged, String> {
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+    let json_path = get_applications_json_path(&app_handle, &build_id)
+        .ok_or("Failed to get applications.json path")?;
+
+    let on_disk = read_applications_json(&json_path)?.applications;
+
+    let state: State<AppState> = app_handle.state();
+    let apps = state
+        .applications
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+    Ok(diff_applications(&on_disk, &apps))
+}
+
+/// Throw away every unsaved edit by reloading applications.json
+#[tauri::command]
+pub async fn discard_all_changes(app_handle: AppHandle) -> Result<(), String> {
+    let build_id = get_build_id(&app_handle).ok_or("Failed to get build_id")?;
+    load_and_store_applications(&app_handle, &build_id)?;
+    let _ = app_handle.emit("applications-changed", ApplicationsChanged::default());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str, name: &str) -> GHUBApp {
+        serde_json::from_value(serde_json::json!({ "applicationId": id, "name": name })).unwrap()
+    }
+
+    #[test]
+    fn undo_and_redo_restore_snapshots() {
+        let mut journal = EditJournal::new(10);
+        let mut apps = vec![app("a", "A")];
+
+        apps[0] = app("a", "A renamed");
+        journal.record(EditSource::Editor, "Rename", Some(app("a", "A")), Some(apps[0].clone()));
+
+        let undone = journal.undo(&mut apps).unwrap();
+        assert_eq!(undone.edits[0].application_id, "a");
+        assert_eq!(apps[0].name, "A");
+
+        journal.redo(&mut apps).unwrap();
+        assert_eq!(apps[0].name, "A renamed");
+        assert!(journal.redo(&mut apps).is_none());
+    }
+
+    #[test]
+    fn undoing_an_addition_removes_the_application() {
+        let mut journal = EditJournal::new(10);
+        let mut apps = vec![app("a", "A")];
+
+        journal.record(EditSource::Editor, "Create", None, Some(app("a", "A")));
+        journal.undo(&mut apps);
+
+        assert!(apps.is_empty());
+    }
+
+    #[test]
+    fn new_edit_clears_redo_and_no_ops_are_ignored() {
+        let mut journal = EditJournal::new(10);
+        let mut apps = vec![app("a", "A2")];
+
+        journal.record(EditSource::Editor, "Rename", Some(app("a", "A")), Some(app("a", "A2")));
+        journal.undo(&mut apps);
+        assert!(journal.state().can_redo);
+
+        journal.record(EditSource::PatchApplier, "Patch", Some(app("a", "A")), Some(app("a", "A3")));
+        assert!(!journal.state().can_redo);
+
+        journal.record(EditSource::Editor, "Nothing", Some(app("a", "A")), Some(app("a", "A")));
+        assert_eq!(journal.state().undo_stack.len(), 1);
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped_at_capacity() {
+        let mut journal = EditJournal::new(2);
+        for name in ["B", "C", "D"] {
+            journal.record(EditSource::Editor, name, Some(app("a", "A")), Some(app("a", name)));
+        }
+
+        let state = journal.state();
+        assert_eq!(state.undo_stack.len(), 2);
+        assert_eq!(state.undo_stack[0].description, "C");
+    }
+
+    #[test]
+    fn forgetting_an_application_drops_its_entries() {
+        let mut journal = EditJournal::new(10);
+        journal.record(EditSource::Editor, "Rename", Some(app("a", "A")), Some(app("a", "A2")));
+        journal.record(EditSource::Editor, "Rename", Some(app("b", "B")), Some(app("b", "B2")));
+        assert_eq!(journal.pending_ids(), HashSet::from(["a".to_string(), "b".to_string()]));
+
+        journal.forget(&HashSet::from(["a"]));
+
+        assert_eq!(journal.pending_ids(), HashSet::from(["b".to_string()]));
+    }
+
+    #[test]
+    fn saving_clears_pending_ids_but_keeps_history() {
+        let mut journal = EditJournal::new(10);
+        let mut apps = vec![app("a", "A2")];
+        journal.record(EditSource::Editor, "Rename", Some(app("a", "A")), Some(app("a", "A2")));
+
+        journal.mark_saved();
+        assert!(journal.pending_ids().is_empty());
+        assert!(journal.state().can_undo);
+
+        journal.undo(&mut apps).unwrap();
+        assert_eq!(journal.pending_ids(), HashSet::from(["a".to_string()]));
+
+        journal.mark_saved();
+        journal.record(EditSource::Editor, "Rename", Some(app("b", "B")), Some(app("b", "B2")));
+        assert_eq!(journal.pending_ids(), HashSet::from(["b".to_string()]));
+    }
+
+    #[test]
+    fn grouped_edits_are_one_undo_step() {
+        let mut journal = EditJournal::new(2);
+        let mut apps = vec![app("a", "A2"), app("b", "B2"), app("c", "C2")];
+
+        journal.record_all(
+            EditSource::Editor,
+            "Bulk edit",
+            vec![
+                (Some(app("a", "A")), Some(app("a", "A2"))),
+                (Some(app("b", "B")), Some(app("b", "B2"))),
+                (Some(app("c", "C")), Some(app("c", "C2"))),
+            ],
+        );
+        assert_eq!(journal.state().undo_stack.len(), 1);
+
+        journal.undo(&mut apps).unwrap();
+        assert_eq!(apps.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), vec!["A", "B", "C"]);
+        assert!(!journal.state().can_undo);
+
+        journal.redo(&mut apps).unwrap();
+        journal.forget(&HashSet::from(["a", "b"]));
+        assert_eq!(journal.pending_ids(), HashSet::from(["c".to_string()]));
+    }
+}
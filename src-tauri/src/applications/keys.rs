@@ -0,0 +1,73 @@
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+
+/// Named keys accepted in command keystrokes, besides letters, digits and
+/// function keys which are matched by pattern
+const NAMED_KEYS: &[&str] = &[
+    // Modifiers
+    "CTRL", "LCTRL", "RCTRL", "SHIFT", "LSHIFT", "RSHIFT", "ALT", "LALT", "RALT", "WIN", "LWIN",
+    "RWIN", "CMD", "LCMD", "RCMD",
+    // Editing and navigation
+    "SPACE", "ENTER", "RETURN", "ESC", "ESCAPE", "TAB", "BACKSPACE", "CAPSLOCK", "INSERT",
+    "DELETE", "HOME", "END", "PAGEUP", "PAGEDOWN", "UP", "DOWN", "LEFT", "RIGHT", "PRINTSCREEN",
+    "SCROLLLOCK", "PAUSE", "NUMLOCK", "MENU",
+    // Punctuation
+    "MINUS", "EQUALS", "LBRACKET", "RBRACKET", "BACKSLASH", "SEMICOLON", "APOSTROPHE", "COMMA",
+    "PERIOD", "SLASH", "GRAVE", "TILDE",
+    // Numpad
+    "NUMPADPLUS", "NUMPADMINUS", "NUMPADMULTIPLY", "NUMPADDIVIDE", "NUMPADDECIMAL", "NUMPADENTER",
+    // Mouse
+    "LMB", "RMB", "MMB", "MOUSE1", "MOUSE2", "MOUSE3", "MOUSE4", "MOUSE5", "WHEELUP", "WHEELDOWN",
+    // Media
+    "VOLUMEUP", "VOLUMEDOWN", "MUTE", "PLAYPAUSE", "NEXTTRACK", "PREVTRACK",
+];
+
+static NAMED_KEY_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| NAMED_KEYS.iter().copied().collect());
+
+/// Uppercase a key name and drop separators, so `Left Shift`, `left_shift`
+/// and `LEFTSHIFT` compare equal
+pub fn normalize_key(name: &str) -> String {
+    let key: String = name
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .flat_map(char::to_uppercase)
+        .collect();
+
+    // Accept spelled-out sides for modifiers
+    for (long, short) in [("LEFT", "L"), ("RIGHT", "R")] {
+        if let Some(rest) = key.strip_prefix(long) {
+            if ["CTRL", "CONTROL", "SHIFT", "ALT", "WIN", "CMD"].contains(&rest) {
+                return format!("{}{}", short, rest.replace("CONTROL", "CTRL"));
+            }
+        }
+    }
+    key.replace("CONTROL", "CTRL")
+}
+
+/// Whether `name` is a key G HUB keystrokes can use
+pub fn is_known_key(name: &str) -> bool {
+    let key = normalize_key(name);
+    let is_char = key.len() == 1 && key.chars().all(|c| c.is_ascii_alphanumeric());
+    let is_numbered = |prefix: &str, max: u32| {
+        key.strip_prefix(prefix)
+            .and_then(|n| n.parse::<u32>().ok())
+            .is_some_and(|n| n <= max && (prefix != "F" || n >= 1))
+    };
+
+    is_char || is_numbered("F", 24) || is_numbered("NUMPAD", 9) || NAMED_KEY_SET.contains(key.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_common_key_names() {
+        for key in ["W", "7", "F12", "space", "Left Shift", "left_ctrl", "NUMPAD5", "MOUSE4"] {
+            assert!(is_known_key(key), "{} should be known", key);
+        }
+        for key in ["", "F0", "F25", "SPACEBAR", "NUMPAD10", "Hyper"] {
+            assert!(!is_known_key(key), "{} should not be known", key);
+        }
+    }
+}
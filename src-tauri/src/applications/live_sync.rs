@@ -0,0 +1,344 @@
+use crate::applications::models::GHUBApp;
+use crate::core::state::AppState;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Payload of the `applications-changed` event
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationsChanged {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl ApplicationsChanged {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    fn ids(&self) -> impl Iterator<Item = &String> {
+        self.added.iter().chain(&self.removed).chain(&self.modified)
+    }
+}
+
+/// Payload of the `applications-conflict` event: G HUB changed applications
+/// that have unsaved edits, which were kept, or left out applications we have
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationsConflict {
+    pub application_ids: Vec<String>,
+    /// Kept applications that were missing from G HUB's full list
+    pub missing_in_ghub: Vec<String>,
+}
+
+/// Result of applying one G HUB message
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncOutcome {
+    pub changes: ApplicationsChanged,
+    /// Applications with unsaved edits that G HUB changed or removed
+    pub conflicts: Vec<String>,
+    /// Applications we have that G HUB's full list didn't include
+    pub missing_in_ghub: Vec<String>,
+}
+
+/// Keep `AppState::applications` in sync with application lists and updates
+/// broadcast by G HUB, emitting `applications-changed` when anything changed.
+/// Applications with unsaved edits, and ones a full list leaves out, are left
+/// alone and reported in an `applications-conflict` event instead.
+pub fn handle_message<R: Runtime>(app_handle: &AppHandle<R>, message: &Value) {
+    if !is_successful(message) {
+        return;
+    }
+
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return;
+    };
+
+    let outcome = {
+        // Always lock applications before the journal
+        let mut apps = match state.applications.lock() {
+            Ok(apps) => apps,
+            Err(e) => {
+                eprintln!("Failed to acquire lock on applications: {}", e);
+                return;
+            }
+        };
+        let mut journal = match state.edit_journal.lock() {
+            Ok(journal) => journal,
+            Err(e) => {
+                eprintln!("Failed to acquire lock on edit journal: {}", e);
+                return;
+            }
+        };
+
+        let outcome = apply_message(&mut apps, message, &journal.pending_ids());
+        // Undone edits of replaced applications would restore stale snapshots
+        let replaced: HashSet<&str> = outcome.changes.ids().map(String::as_str).collect();
+        journal.forget(&replaced);
+        outcome
+    };
+
+    if !outcome.changes.is_empty() {
+        let _ = app_handle.emit("applications-changed", &outcome.changes);
+    }
+    if !outcome.conflicts.is_empty() || !outcome.missing_in_ghub.is_empty() {
+        let _ = app_handle.emit(
+            "applications-conflict",
+            ApplicationsConflict {
+                application_ids: outcome.conflicts,
+                missing_in_ghub: outcome.missing_in_ghub,
+            },
+        );
+    }
+}
+
+/// Apply a G HUB `/applications` or `/application` message to a list of
+/// applications, merging by id. Applications in `pending` have unsaved edits
+/// and are kept as they are; if G HUB changed them they are reported as conflicts.
+/// A full list never removes anything: applications it leaves out may be local
+/// custom apps G HUB hasn't loaded yet or entries that failed to parse, so they
+/// are reported as missing in G HUB. Only an explicit `DELETE` removes one.
+pub fn apply_message(apps: &mut Vec<GHUBApp>, message: &Value, pending: &HashSet<String>) -> SyncOutcome {
+    let verb = message.get("verb").and_then(|v| v.as_str()).unwrap_or_default();
+    let path = message.get("path").and_then(|p| p.as_str()).unwrap_or_default();
+    let Some(payload) = message.get("payload") else {
+        return SyncOutcome::default();
+    };
+
+    let mut outcome = SyncOutcome::default();
+    match (verb, path) {
+        (_, "/applications") => {
+            let Some(entries) = payload.get("applications").and_then(|a| a.as_array()) else {
+                return outcome;
+            };
+            let incoming: Vec<GHUBApp> = entries.iter().filter_map(parse_application).collect();
+
+            for app in &incoming {
+                upsert_application(apps, app.clone(), pending, &mut outcome);
+            }
+            outcome.missing_in_ghub = apps
+                .iter()
+                .map(|a| a.application_id.clone())
+                .filter(|id| !incoming.iter().any(|a| a.application_id == *id))
+                .collect();
+        }
+        ("DELETE", "/application") => {
+            if let Some(id) = payload
+                .get("applicationId")
+                .or_else(|| payload.get("id"))
+                .and_then(|id| id.as_str())
+            {
+                remove_application(apps, id, pending, &mut outcome);
+            }
+        }
+        (_, "/application") => {
+            if let Some(updated) = parse_application(payload) {
+                upsert_application(apps, updated, pending, &mut outcome);
+            }
+        }
+        _ => {}
+    }
+    outcome
+}
+
+/// Compare two application lists by `application_id`
+pub fn diff_applications(old: &[GHUBApp], new: &[GHUBApp]) -> ApplicationsChanged {
+    let mut changes = ApplicationsChanged::default();
+
+    for app in new {
+        match old.iter().find(|a| a.application_id == app.application_id) {
+            None => changes.added.push(app.application_id.clone()),
+            Some(existing) if existing != app => changes.modified.push(app.application_id.clone()),
+            Some(_) => {}
+        }
+    }
+
+    for app in old {
+        if !new.iter().any(|a| a.application_id == app.application_id) {
+            changes.removed.push(app.application_id.clone());
+        }
+    }
+
+    changes
+}
+
+fn upsert_application(
+    apps: &mut Vec<GHUBApp>,
+    updated: GHUBApp,
+    pending: &HashSet<String>,
+    outcome: &mut SyncOutcome,
+) {
+    let id = updated.application_id.clone();
+    match apps.iter_mut().find(|a| a.application_id == id) {
+        Some(existing) if *existing == updated => {}
+        Some(_) if pending.contains(&id) => outcome.conflicts.push(id),
+        Some(existing) => {
+            *existing = updated;
+            outcome.changes.modified.push(id);
+        }
+        // Deleted locally but still in G HUB
+        None if pending.contains(&id) => outcome.conflicts.push(id),
+        None => {
+            apps.push(updated);
+            outcome.changes.added.push(id);
+        }
+    }
+}
+
+fn remove_application(apps: &mut Vec<GHUBApp>, id: &str, pending: &HashSet<String>, outcome: &mut SyncOutcome) {
+    if !apps.iter().any(|a| a.application_id == id) {
+        return;
+    }
+    if pending.contains(id) {
+        outcome.conflicts.push(id.to_string());
+    } else {
+        apps.retain(|a| a.application_id != id);
+        outcome.changes.removed.push(id.to_string());
+    }
+}
+
+/// Parse an application object, skipping entries without an id or name
+fn parse_application(value: &Value) -> Option<GHUBApp> {
+    serde_json::from_value::<GHUBApp>(value.clone())
+        .ok()
+        .filter(|app| !app.application_id.is_empty() && !app.name.is_empty())
+}
+
+/// Responses carry a `result.code`; broadcast events have no result at all
+fn is_successful(message: &Value) -> bool {
+    match message.get("result").and_then(|r| r.get("code")) {
+        Some(code) => code.as_str() == Some("SUCCESS"),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::applications_json::applications_to_json;
+    use serde_json::json;
+
+    fn app(id: &str, name: &str) -> Value {
+        json!({ "applicationId": id, "name": name })
+    }
+
+    fn parsed(values: &[Value]) -> Vec<GHUBApp> {
+        values.iter().filter_map(parse_application).collect()
+    }
+
+    #[test]
+    fn full_list_reports_added_modified_and_missing() {
+        let mut apps = parsed(&[app("a", "A"), app("b", "B"), app("c", "C")]);
+        let message = json!({
+            "verb": "GET",
+            "path": "/applications",
+            "payload": { "applications": [app("a", "A"), app("b", "B renamed"), app("d", "D")] }
+        });
+
+        let outcome = apply_message(&mut apps, &message, &HashSet::new());
+
+        assert_eq!(outcome.changes.added, vec!["d"]);
+        assert!(outcome.changes.removed.is_empty());
+        assert_eq!(outcome.changes.modified, vec!["b"]);
+        assert_eq!(outcome.missing_in_ghub, vec!["c"]);
+        assert_eq!(apps.len(), 4);
+    }
+
+    #[test]
+    fn local_only_applications_survive_a_full_list_and_a_save() {
+        let mut apps = parsed(&[app("a", "A"), app("custom", "Custom game")]);
+        let message = json!({
+            "path": "/applications",
+            "payload": { "applications": [app("a", "A"), { "applicationId": "custom", "name": 42 }] }
+        });
+
+        let outcome = apply_message(&mut apps, &message, &HashSet::new());
+        assert!(outcome.changes.is_empty());
+        assert_eq!(outcome.missing_in_ghub, vec!["custom"]);
+
+        let saved = applications_to_json(&apps, &[]).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[1]["applicationId"], "custom");
+        assert_eq!(saved[1]["name"], "Custom game");
+    }
+
+    #[test]
+    fn single_application_is_upserted() {
+        let mut apps = parsed(&[app("a", "A")]);
+
+        let changes = apply_message(
+            &mut apps,
+            &json!({ "verb": "SET", "path": "/application", "payload": app("a", "A2") }),
+            &HashSet::new(),
+        )
+        .changes;
+        assert_eq!(changes.modified, vec!["a"]);
+        assert_eq!(apps[0].name, "A2");
+
+        let changes = apply_message(
+            &mut apps,
+            &json!({ "path": "/application", "payload": app("b", "B") }),
+            &HashSet::new(),
+        )
+        .changes;
+        assert_eq!(changes.added, vec!["b"]);
+
+        // Receiving the same application again is not a change
+        let changes = apply_message(
+            &mut apps,
+            &json!({ "path": "/application", "payload": app("b", "B") }),
+            &HashSet::new(),
+        )
+        .changes;
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn delete_removes_by_id() {
+        let mut apps = parsed(&[app("a", "A"), app("b", "B")]);
+
+        let changes = apply_message(
+            &mut apps,
+            &json!({ "verb": "DELETE", "path": "/application", "payload": { "id": "a" } }),
+            &HashSet::new(),
+        )
+        .changes;
+
+        assert_eq!(changes.removed, vec!["a"]);
+        assert_eq!(apps.len(), 1);
+    }
+
+    #[test]
+    fn applications_with_unsaved_edits_are_kept_and_reported() {
+        let mut apps = parsed(&[app("a", "A edited"), app("b", "B"), app("c", "C edited")]);
+        let pending: HashSet<String> = ["a".to_string(), "c".to_string()].into();
+        let message = json!({
+            "path": "/applications",
+            "payload": { "applications": [app("a", "A from G HUB"), app("b", "B2")] }
+        });
+
+        let outcome = apply_message(&mut apps, &message, &pending);
+
+        assert_eq!(outcome.changes.modified, vec!["b"]);
+        assert!(outcome.changes.removed.is_empty());
+        assert_eq!(outcome.conflicts, vec!["a"]);
+        assert_eq!(outcome.missing_in_ghub, vec!["c"]);
+        assert_eq!(apps[0].name, "A edited");
+        assert_eq!(apps[1].name, "B2");
+        assert_eq!(apps.len(), 3);
+    }
+
+    #[test]
+    fn failed_responses_are_ignored() {
+        let message = json!({
+            "path": "/applications",
+            "result": { "code": "INVALID_ARG" },
+            "payload": {}
+        });
+        assert!(!is_successful(&message));
+        assert!(is_successful(&json!({ "path": "/applications", "payload": {} })));
+    }
+}
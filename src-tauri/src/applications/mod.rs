@@ -1,5 +1,19 @@
+pub mod app_validation;
 pub mod applications_json;
+pub mod backups;
+pub mod builds;
+pub mod bulk_edit;
+pub mod command_library;
+pub mod bundles;
+pub mod custom_apps;
+pub mod diff;
+pub mod discovery;
+pub mod journal;
+pub mod keys;
+pub mod live_sync;
 pub mod models;
 pub mod paths;
+pub mod posters;
+pub mod query;
 pub mod validation;
 pub mod ghub_version;
@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApplicationsData {
     pub applications: Vec<GHUBApp>,
+    /// Top-level fields g-hauler doesn't model, written back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GHUBApp {
     #[serde(default)]
@@ -23,23 +27,95 @@ pub struct GHUBApp {
     pub poster_url: String,
     #[serde(default)]
     pub version: u32,
+    /// Fields g-hauler doesn't model, written back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// An applications.json entry that couldn't be parsed into a `GHUBApp`,
+/// kept verbatim so saving writes it back unchanged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnparsedApplication {
+    /// Position in the file's `applications` array
+    pub index: usize,
+    pub reason: String,
+    pub raw: Value,
+}
+
+/// Applications read from an applications.json file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedApplications {
+    pub applications: Vec<GHUBApp>,
+    pub unparsed: Vec<UnparsedApplication>,
+}
+
+impl ParsedApplications {
+    /// Number of entries in the file's `applications` array
+    pub fn total(&self) -> usize {
+        self.applications.len() + self.unparsed.len()
+    }
+
+    pub fn report(&self) -> ApplicationsLoadReport {
+        ApplicationsLoadReport::new(&self.applications, &self.unparsed)
+    }
+}
+
+/// Summary of a load, telling the UI which entries were skipped and why
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationsLoadReport {
+    pub loaded: usize,
+    pub skipped: Vec<SkippedApplication>,
+    /// Detections of loaded applications that g-hauler doesn't recognise
+    pub unknown_detections: Vec<UnknownDetectionSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedApplication {
+    pub index: usize,
+    pub reason: String,
+    pub application_id: Option<String>,
+    pub name: Option<String>,
+}
+
+impl ApplicationsLoadReport {
+    pub fn new(applications: &[GHUBApp], unparsed: &[UnparsedApplication]) -> Self {
+        let field = |raw: &Value, key: &str| raw.get(key).and_then(|v| v.as_str()).map(String::from);
+        Self {
+            loaded: applications.len(),
+            unknown_detections: summarize_unknown_detections(applications),
+            skipped: unparsed
+                .iter()
+                .map(|entry| SkippedApplication {
+                    index: entry.index,
+                    reason: entry.reason.clone(),
+                    application_id: field(&entry.raw, "applicationId"),
+                    name: field(&entry.raw, "name"),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CategoryColor {
     pub hex: String,
     pub tag: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Command {
     pub category: String,
     pub keystroke: Vec<String>,
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+// Known variants deny unknown fields so anything they can't represent exactly
+// falls through to `Unknown` and is written back unchanged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
 pub enum Detection {
     Steam {
         steam: SteamApp,
@@ -70,51 +146,233 @@ pub enum Detection {
     Glob {
         glob: String,
     },
-    // Catch-all for unknown detection types
-    Unknown(serde_json::Value),
+    // Catch-all for unknown detection types and malformed known ones
+    Unknown(UnknownDetection),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The kind of a `Detection`, named like its JSON key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub enum DetectionType {
+    Steam,
+    WinRegistry,
+    EpicGames,
+    OsxBundle,
+    Uplay,
+    GogGalaxy,
+    RiotGames,
+    Glob,
+}
+
+impl Detection {
+    /// `None` for unknown detection types
+    pub fn detection_type(&self) -> Option<DetectionType> {
+        match self {
+            Detection::Steam { .. } => Some(DetectionType::Steam),
+            Detection::WinRegistry { .. } => Some(DetectionType::WinRegistry),
+            Detection::EpicGames { .. } => Some(DetectionType::EpicGames),
+            Detection::OsxBundle { .. } => Some(DetectionType::OsxBundle),
+            Detection::Uplay { .. } => Some(DetectionType::Uplay),
+            Detection::GogGalaxy { .. } => Some(DetectionType::GogGalaxy),
+            Detection::RiotGames { .. } => Some(DetectionType::RiotGames),
+            Detection::Glob { .. } => Some(DetectionType::Glob),
+            Detection::Unknown(_) => None,
+        }
+    }
+}
+
+impl DetectionType {
+    pub const ALL: [DetectionType; 8] = [
+        DetectionType::Steam,
+        DetectionType::WinRegistry,
+        DetectionType::EpicGames,
+        DetectionType::OsxBundle,
+        DetectionType::Uplay,
+        DetectionType::GogGalaxy,
+        DetectionType::RiotGames,
+        DetectionType::Glob,
+    ];
+
+    /// The key of this detection in applications.json
+    pub fn key(self) -> &'static str {
+        match self {
+            DetectionType::Steam => "steam",
+            DetectionType::WinRegistry => "winRegistry",
+            DetectionType::EpicGames => "epicGames",
+            DetectionType::OsxBundle => "osxBundle",
+            DetectionType::Uplay => "uplay",
+            DetectionType::GogGalaxy => "gogGalaxy",
+            DetectionType::RiotGames => "riotGames",
+            DetectionType::Glob => "glob",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<DetectionType> {
+        Self::ALL.into_iter().find(|t| t.key() == key)
+    }
+}
+
+/// A detection g-hauler couldn't parse, kept as the exact JSON from
+/// applications.json so saving writes it back unchanged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UnknownDetection(pub Value);
+
+impl UnknownDetection {
+    /// The detection's key, e.g. `eaApp` for `{ "eaApp": { ... } }`; `None` if it isn't an object
+    pub fn key(&self) -> Option<&str> {
+        self.0.as_object()?.keys().next().map(String::as_str)
+    }
+
+    /// A known type whose contents don't parse, e.g. `{ "steam": { "appid": "1" } }`
+    pub fn malformed_type(&self) -> Option<DetectionType> {
+        self.key().and_then(DetectionType::from_key)
+    }
+
+    /// Why a malformed known detection doesn't parse
+    pub fn parse_error(&self) -> Option<String> {
+        let detection_type = self.malformed_type()?;
+        let value = self.0.get(detection_type.key()).cloned().unwrap_or(Value::Null);
+        let result = match detection_type {
+            DetectionType::Steam => serde_json::from_value::<SteamApp>(value).map(|_| ()),
+            DetectionType::WinRegistry => serde_json::from_value::<WinRegistry>(value).map(|_| ()),
+            DetectionType::EpicGames => serde_json::from_value::<EpicGames>(value).map(|_| ()),
+            DetectionType::OsxBundle => serde_json::from_value::<OsxBundle>(value).map(|_| ()),
+            DetectionType::Uplay => serde_json::from_value::<Uplay>(value).map(|_| ()),
+            DetectionType::GogGalaxy => serde_json::from_value::<GogGalaxy>(value).map(|_| ()),
+            DetectionType::RiotGames => serde_json::from_value::<RiotGames>(value).map(|_| ()),
+            DetectionType::Glob => serde_json::from_value::<String>(value).map(|_| ()),
+        };
+        Some(match result {
+            Err(e) => e.to_string(),
+            // Parses on its own, so something else in the object is off
+            Ok(()) => "unexpected fields next to the detection".to_string(),
+        })
+    }
+}
+
+/// How often one unknown detection key occurs across applications
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownDetectionSummary {
+    /// `None` for detections that aren't JSON objects
+    pub key: Option<String>,
+    /// The key names a known type but its contents don't parse
+    pub malformed: bool,
+    pub count: usize,
+    pub application_ids: Vec<String>,
+}
+
+/// Group the unknown detections of `apps` by key, most frequent first
+pub fn summarize_unknown_detections(apps: &[GHUBApp]) -> Vec<UnknownDetectionSummary> {
+    let mut summaries: Vec<UnknownDetectionSummary> = Vec::new();
+    for app in apps {
+        for detection in &app.detection {
+            let Detection::Unknown(unknown) = detection else {
+                continue;
+            };
+            let key = unknown.key().map(String::from);
+            let summary = match summaries.iter_mut().position(|s| s.key == key) {
+                Some(i) => &mut summaries[i],
+                None => {
+                    summaries.push(UnknownDetectionSummary {
+                        malformed: unknown.malformed_type().is_some(),
+                        key,
+                        count: 0,
+                        application_ids: Vec::new(),
+                    });
+                    summaries.last_mut().unwrap()
+                }
+            };
+            summary.count += 1;
+            if !summary.application_ids.contains(&app.application_id) {
+                summary.application_ids.push(app.application_id.clone());
+            }
+        }
+    }
+    summaries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    summaries
+}
+
+impl GHUBApp {
+    pub fn has_detection_type(&self, detection_type: DetectionType) -> bool {
+        self.detection
+            .iter()
+            .any(|d| d.detection_type() == Some(detection_type))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SteamApp {
     pub app_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct WinRegistry {
     pub executable: String,
     pub registry_key: String,
     pub registry_path: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct EpicGames {
     pub app_name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct OsxBundle {
     pub bundle_id: String,
     pub bundle_path: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Uplay {
     pub app_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct GogGalaxy {
     pub product_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct RiotGames {
     pub app_name: String,
 }
+
+/// `<data>/current.json`, which names the active build under `depots`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentJson {
+    pub build_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<Value>,
+    /// Fields g-hauler doesn't model
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `version.json`, at the top of the data directory and in some builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionJson {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<Value>,
+    /// Fields g-hauler doesn't model
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
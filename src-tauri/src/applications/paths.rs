@@ -1,6 +1,6 @@
 use crate::core::constants::STORE_KEY_DATA_PATH;
 use crate::core::store::get_store_key;
-use std::fs;
+use crate::applications::ghub_version::read_current_json;
 use std::path::PathBuf;
 use tauri::AppHandle;
 
@@ -12,9 +12,7 @@ pub fn get_data_path(app_handle: &AppHandle) -> Option<String> {
 
 pub fn get_build_id(app_handle: &AppHandle) -> Option<String> {
     let current_json_path = get_current_json_path(app_handle)?;
-    let content = fs::read_to_string(current_json_path).ok()?;
-    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
-    json.get("buildId")?.as_str().map(|s| s.to_string())
+    read_current_json(&current_json_path).ok().map(|c| c.build_id)
 }
 
 pub fn get_current_json_path(app_handle: &AppHandle) -> Option<PathBuf> {
@@ -27,6 +25,11 @@ pub fn get_version_json_path(app_handle: &AppHandle) -> Option<PathBuf> {
     Some(PathBuf::from(data_path).join("version.json"))
 }
 
+pub fn get_depots_dir_path(app_handle: &AppHandle) -> Option<PathBuf> {
+    let data_path = get_data_path(app_handle)?;
+    Some(PathBuf::from(data_path).join("depots"))
+}
+
 pub fn get_build_dir_path(app_handle: &AppHandle, build_id: &str) -> Option<PathBuf> {
     let data_path = get_data_path(app_handle)?;
     Some(PathBuf::from(data_path).join("depots").join(build_id))
@@ -0,0 +1,481 @@
+use crate::applications::applications_json::save_applications_to_disk;
+use crate::applications::journal::{record_edit, EditSource};
+use crate::applications::models::GHUBApp;
+use crate::applications::paths::{get_build_id, get_images_dir_path};
+use crate::core::constants::{POSTER_OVERRIDES_DIRNAME, POSTER_OVERRIDES_FILENAME};
+use crate::core::state::{get_stored_applications, AppState};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+/// G HUB shows posters as 2:3 portrait JPEGs
+pub const POSTER_WIDTH: u32 = 600;
+pub const POSTER_HEIGHT: u32 = 900;
+const POSTER_JPEG_QUALITY: u8 = 90;
+
+/// Folder inside the images directory that replaced posters are copied to
+const POSTER_OVERRIDE_SUBDIR: &str = "g-hauler";
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif", "bmp"];
+
+/// Strip a `file://` scheme and normalise separators. `None` for remote URLs.
+fn poster_local_path(poster_url: &str) -> Option<String> {
+    let url = poster_url.trim();
+    let remote = url.contains("://") && !url.starts_with("file://");
+    if url.is_empty() || remote || url.starts_with("data:") {
+        return None;
+    }
+
+    let path = url.strip_prefix("file://").unwrap_or(url);
+    // `file:///C:/...` leaves a leading slash before the drive letter
+    let path = match path.strip_prefix('/') {
+        Some(rest) if rest.get(1..2) == Some(":") => rest,
+        _ => path,
+    };
+    let decoded = urlencoding::decode(path)
+        .map(|p| p.into_owned())
+        .unwrap_or_else(|_| path.to_string());
+    Some(decoded.replace('\\', "/"))
+}
+
+/// Path of a poster relative to the G HUB images directory, for poster URLs
+/// that point into it (e.g. `file://images/posters/game.jpg` -> `posters/game.jpg`)
+pub fn poster_relative_path(poster_url: &str) -> Option<PathBuf> {
+    let path = poster_local_path(poster_url)?;
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty() && *p != ".").collect();
+
+    let relative: Vec<&str> = match parts.iter().rposition(|p| p.eq_ignore_ascii_case("images")) {
+        Some(index) => parts[index + 1..].to_vec(),
+        None if Path::new(&path).is_absolute() || path.get(1..2) == Some(":") => return None,
+        None => parts,
+    };
+
+    if relative.is_empty() || relative.contains(&"..") {
+        return None;
+    }
+    Some(relative.iter().collect())
+}
+
+/// Whether a relative path stays inside the directory it is joined to
+pub fn is_safe_relative_path(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Resolve a poster URL to a file: absolute local paths as-is, everything else
+/// relative to the images directory
+pub fn resolve_poster_path(images_dir: &Path, poster_url: &str) -> Option<PathBuf> {
+    let local = poster_local_path(poster_url)?;
+    let absolute = PathBuf::from(&local);
+    if absolute.is_absolute() && absolute.exists() {
+        return Some(absolute);
+    }
+
+    poster_relative_path(poster_url).map(|relative| images_dir.join(relative))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PosterStatus {
+    Ok,
+    /// No poster URL set
+    None,
+    /// Hosted elsewhere; not checked
+    Remote,
+    /// The URL points at a file that doesn't exist
+    Missing,
+    /// The file exists but can't be read as an image
+    Broken,
+}
+
+/// An image file in the G HUB images directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PosterFile {
+    /// Relative to the images directory, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Applications whose poster URL resolves to this file
+    pub used_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PosterCheck {
+    pub application_id: String,
+    pub name: String,
+    pub poster_url: String,
+    pub path: Option<PathBuf>,
+    pub status: PosterStatus,
+}
+
+/// A poster the user replaced; the converted image is kept in the app data
+/// directory so it can be copied into new builds after G HUB updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PosterOverride {
+    pub application_id: String,
+    pub file_name: String,
+    pub poster_url: String,
+    pub replaced_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PosterOverridesData {
+    pub overrides: Vec<PosterOverride>,
+}
+
+/// Image files under `images_dir`, as paths relative to it
+pub fn collect_poster_files(images_dir: &Path) -> Vec<PathBuf> {
+    fn walk(dir: &Path, root: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.is_dir() {
+                walk(&path, root, files);
+            } else if is_image_file(&path) {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    files.push(relative.to_path_buf());
+                }
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(images_dir, images_dir, &mut files);
+    files.sort();
+    files
+}
+
+fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Whether the file at `path` is a readable image
+pub fn check_poster_file(path: &Path) -> PosterStatus {
+    match fs::metadata(path) {
+        Err(_) => PosterStatus::Missing,
+        Ok(meta) if !meta.is_file() || meta.len() == 0 => PosterStatus::Broken,
+        Ok(_) => match image::image_dimensions(path) {
+            Ok(_) => PosterStatus::Ok,
+            Err(_) => PosterStatus::Broken,
+        },
+    }
+}
+
+pub fn check_poster(images_dir: &Path, app: &GHUBApp) -> PosterCheck {
+    let url = app.poster_url.trim();
+    let path = resolve_poster_path(images_dir, url);
+    let status = match &path {
+        Some(path) => check_poster_file(path),
+        None if url.is_empty() => PosterStatus::None,
+        None if url.contains("://") && !url.starts_with("file://") => PosterStatus::Remote,
+        // A local URL we can't place inside the images directory
+        None => PosterStatus::Missing,
+    };
+
+    PosterCheck {
+        application_id: app.application_id.clone(),
+        name: app.name.clone(),
+        poster_url: app.poster_url.clone(),
+        path,
+        status,
+    }
+}
+
+/// Decode any supported image, crop it to G HUB's poster shape and write it as JPEG
+pub fn convert_poster(source: &Path, destination: &Path) -> Result<(), String> {
+    let image = image::ImageReader::open(source)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .with_guessed_format()
+        .map_err(|e| format!("Failed to read image: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let poster = image
+        .resize_to_fill(POSTER_WIDTH, POSTER_HEIGHT, FilterType::Lanczos3)
+        .to_rgb8();
+
+    let mut file = fs::File::create(destination)
+        .map_err(|e| format!("Failed to create poster file: {}", e))?;
+    JpegEncoder::new_with_quality(&mut file, POSTER_JPEG_QUALITY)
+        .encode_image(&poster)
+        .map_err(|e| format!("Failed to encode poster: {}", e))
+}
+
+/// Poster path inside the images directory and the URL G HUB uses for it
+pub fn poster_override_location(application_id: &str) -> (PathBuf, String) {
+    let file_name = format!("{}.jpg", application_id);
+    (
+        PathBuf::from(POSTER_OVERRIDE_SUBDIR).join(&file_name),
+        format!("file://images/{}/{}", POSTER_OVERRIDE_SUBDIR, file_name),
+    )
+}
+
+fn current_images_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let build_id = get_build_id(app_handle).ok_or("Failed to get build_id")?;
+    get_images_dir_path(app_handle, &build_id).ok_or_else(|| "Failed to get images directory".to_string())
+}
+
+fn get_poster_overrides_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        .join(POSTER_OVERRIDES_DIRNAME);
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create posters directory: {}", e))?;
+    Ok(dir)
+}
+
+pub fn load_poster_overrides(app_handle: &AppHandle) -> Result<PosterOverridesData, String> {
+    let path = get_poster_overrides_dir(app_handle)?.join(POSTER_OVERRIDES_FILENAME);
+    if !path.exists() {
+        return Ok(PosterOverridesData::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read poster_overrides.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse poster_overrides.json: {}", e))
+}
+
+fn save_poster_overrides(app_handle: &AppHandle, data: &PosterOverridesData) -> Result<(), String> {
+    let path = get_poster_overrides_dir(app_handle)?.join(POSTER_OVERRIDES_FILENAME);
+    let content = serde_json::to_string_pretty(data)
+        .map_err(|e| format!("Failed to serialize poster overrides: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write poster_overrides.json: {}", e))
+}
+
+/// Copy a saved poster into the images directory
+fn install_poster(app_handle: &AppHandle, images_dir: &Path, record: &PosterOverride) -> Result<(), String> {
+    let source = get_poster_overrides_dir(app_handle)?.join(&record.file_name);
+    let (relative, _) = poster_override_location(&record.application_id);
+    let destination = images_dir.join(relative);
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create poster directory: {}", e))?;
+    }
+    fs::copy(&source, &destination).map_err(|e| format!("Failed to copy poster: {}", e))?;
+    Ok(())
+}
+
+/// Point an application at a new poster URL, recording the edit. Returns false
+/// if it already used that URL.
+fn set_poster_url(app_handle: &AppHandle, application_id: &str, poster_url: &str) -> Result<bool, String> {
+    let (before, after) = {
+        let state: State<AppState> = app_handle.state();
+        let mut apps = state
+            .applications
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+        let app = apps
+            .iter_mut()
+            .find(|a| a.application_id == application_id)
+            .ok_or_else(|| format!("Application with ID '{}' not found", application_id))?;
+        if app.poster_url == poster_url {
+            return Ok(false);
+        }
+
+        let before = app.clone();
+        app.poster_url = poster_url.to_string();
+        (before, app.clone())
+    };
+
+    let description = format!("Replace poster for {}", after.name);
+    record_edit(app_handle, EditSource::Editor, &description, Some(before), Some(after))?;
+    Ok(true)
+}
+
+/// Copy replaced posters into the current build and restore their poster URLs,
+/// e.g. after a G HUB update. Returns the ids of applications that were changed.
+pub async fn reapply_poster_overrides(app_handle: &AppHandle) -> Result<Vec<String>, String> {
+    let overrides = load_poster_overrides(app_handle)?;
+    if overrides.overrides.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let images_dir = current_images_dir(app_handle)?;
+    let mut changed = Vec::new();
+
+    for record in &overrides.overrides {
+        let result = install_poster(app_handle, &images_dir, record)
+            .and_then(|_| set_poster_url(app_handle, &record.application_id, &record.poster_url));
+        match result {
+            Ok(true) => changed.push(record.application_id.clone()),
+            Ok(false) => {}
+            Err(e) => eprintln!("Warning: Failed to restore poster for '{}': {}", record.application_id, e),
+        }
+    }
+
+    if !changed.is_empty() {
+        save_applications_to_disk(app_handle.clone()).await?;
+    }
+    Ok(changed)
+}
+
+/// List image files in the current build's images directory and which applications use them
+#[tauri::command]
+pub async fn poster_list_files(app_handle: AppHandle) -> Result<Vec<PosterFile>, String> {
+    let images_dir = current_images_dir(&app_handle)?;
+    let apps = get_stored_applications(&app_handle)?;
+
+    let mut used_by: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    for app in &apps {
+        if let Some(path) = resolve_poster_path(&images_dir, &app.poster_url) {
+            used_by.entry(path).or_default().push(app.application_id.clone());
+        }
+    }
+
+    Ok(collect_poster_files(&images_dir)
+        .into_iter()
+        .map(|relative| {
+            let absolute = images_dir.join(&relative);
+            PosterFile {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                size: fs::metadata(&absolute).map(|m| m.len()).unwrap_or(0),
+                used_by: used_by.remove(&absolute).unwrap_or_default(),
+            }
+        })
+        .collect())
+}
+
+/// Resolve and check every application's poster
+#[tauri::command]
+pub async fn poster_check_all(app_handle: AppHandle) -> Result<Vec<PosterCheck>, String> {
+    let images_dir = current_images_dir(&app_handle)?;
+    let apps = get_stored_applications(&app_handle)?;
+    Ok(apps.iter().map(|app| check_poster(&images_dir, app)).collect())
+}
+
+/// Replace an application's poster with a user image. The image is converted,
+/// copied into the images directory and re-copied after G HUB updates.
+#[tauri::command]
+pub async fn poster_replace(
+    app_handle: AppHandle,
+    application_id: String,
+    source_path: String,
+) -> Result<PosterCheck, String> {
+    let id_path = Path::new(&application_id);
+    if application_id.is_empty() || !is_safe_relative_path(id_path) || id_path.components().count() != 1 {
+        return Err(format!("Invalid application id '{}'", application_id));
+    }
+    if !get_stored_applications(&app_handle)?
+        .iter()
+        .any(|a| a.application_id == application_id)
+    {
+        return Err(format!("Application with ID '{}' not found", application_id));
+    }
+
+    let images_dir = current_images_dir(&app_handle)?;
+    let (_, poster_url) = poster_override_location(&application_id);
+    let file_name = format!("{}.jpg", application_id);
+
+    let stored = get_poster_overrides_dir(&app_handle)?.join(&file_name);
+    convert_poster(Path::new(&source_path), &stored)?;
+
+    let record = PosterOverride {
+        application_id: application_id.clone(),
+        file_name,
+        poster_url: poster_url.clone(),
+        replaced_at: chrono::Utc::now().to_rfc3339(),
+    };
+    install_poster(&app_handle, &images_dir, &record)?;
+
+    let mut overrides = load_poster_overrides(&app_handle)?;
+    overrides.overrides.retain(|o| o.application_id != application_id);
+    overrides.overrides.push(record);
+    save_poster_overrides(&app_handle, &overrides)?;
+
+    set_poster_url(&app_handle, &application_id, &poster_url)?;
+
+    let apps = get_stored_applications(&app_handle)?;
+    let app = apps
+        .iter()
+        .find(|a| a.application_id == application_id)
+        .ok_or_else(|| format!("Application with ID '{}' not found", application_id))?;
+    Ok(check_poster(&images_dir, app))
+}
+
+/// Stop restoring a replaced poster after updates; the current files are left alone
+#[tauri::command]
+pub async fn poster_forget_override(app_handle: AppHandle, application_id: String) -> Result<(), String> {
+    let mut overrides = load_poster_overrides(&app_handle)?;
+    let Some(index) = overrides
+        .overrides
+        .iter()
+        .position(|o| o.application_id == application_id)
+    else {
+        return Ok(());
+    };
+
+    let record = overrides.overrides.remove(index);
+    let stored = get_poster_overrides_dir(&app_handle)?.join(&record.file_name);
+    if stored.exists() {
+        fs::remove_file(&stored).map_err(|e| format!("Failed to delete saved poster: {}", e))?;
+    }
+    save_poster_overrides(&app_handle, &overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths_are_taken_from_the_images_directory() {
+        assert_eq!(
+            poster_relative_path("file://images/posters/gta5.jpg"),
+            Some(PathBuf::from("posters/gta5.jpg"))
+        );
+        assert_eq!(
+            poster_relative_path("file:///C:/ProgramData/LGHUB/depots/1/core_apps/images/posters/a%20b.png"),
+            Some(PathBuf::from("posters/a b.png"))
+        );
+        assert_eq!(poster_relative_path("posters/custom.jpg"), Some(PathBuf::from("posters/custom.jpg")));
+    }
+
+    #[test]
+    fn remote_and_escaping_urls_are_rejected() {
+        assert_eq!(poster_relative_path("https://example.com/poster.jpg"), None);
+        assert_eq!(poster_relative_path(""), None);
+        assert_eq!(poster_relative_path("file://images/../../secret.txt"), None);
+        assert!(!is_safe_relative_path(Path::new("../x.jpg")));
+        assert!(is_safe_relative_path(Path::new("posters/x.jpg")));
+    }
+
+    #[test]
+    fn lists_image_files_and_flags_missing_or_broken_posters() {
+        let images_dir = std::env::temp_dir().join(format!("g-hauler-posters-{}", std::process::id()));
+        fs::create_dir_all(images_dir.join("posters")).unwrap();
+        fs::write(images_dir.join("posters/empty.jpg"), b"").unwrap();
+        fs::write(images_dir.join("posters/notes.txt"), b"not an image").unwrap();
+
+        assert_eq!(collect_poster_files(&images_dir), vec![PathBuf::from("posters/empty.jpg")]);
+
+        let app = |url: &str| -> GHUBApp {
+            serde_json::from_value(serde_json::json!({ "applicationId": "a", "name": "A", "posterUrl": url }))
+                .unwrap()
+        };
+        let status = |url: &str| check_poster(&images_dir, &app(url)).status;
+        assert_eq!(status("file://images/posters/empty.jpg"), PosterStatus::Broken);
+        assert_eq!(status("file://images/posters/gone.jpg"), PosterStatus::Missing);
+        assert_eq!(status("https://example.com/a.jpg"), PosterStatus::Remote);
+        assert_eq!(status(""), PosterStatus::None);
+
+        fs::remove_dir_all(&images_dir).unwrap();
+    }
+
+    #[test]
+    fn override_location_is_inside_the_images_directory() {
+        let (relative, url) = poster_override_location("abc");
+        assert_eq!(poster_relative_path(&url), Some(relative.clone()));
+        assert!(is_safe_relative_path(&relative));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::applications::models::{DetectionType, GHUBApp};
+use crate::core::state::get_stored_applications;
+use crate::ghub_game_patches::persistence::load_applied_patches;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortField {
+    /// Best search match first, falling back to name when there is no search
+    #[default]
+    Relevance,
+    Name,
+    ApplicationId,
+    DetectionCount,
+    Version,
+}
+
+/// Filters, sorting and paging for the application list; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApplicationQuery {
+    /// Fuzzy match against the application name
+    pub search: Option<String>,
+    /// Every one of these detection types must be present
+    pub has_detections: Vec<DetectionType>,
+    /// None of these detection types may be present
+    pub missing_detections: Vec<DetectionType>,
+    /// Whether detection patches have been applied to the application
+    pub patched: Option<bool>,
+    pub has_poster: Option<bool>,
+    pub sort_by: SortField,
+    pub descending: bool,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationQueryResult {
+    pub items: Vec<GHUBApp>,
+    /// Number of matches before paging
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// Score how well `query` matches `name`, or `None` if it doesn't match at all.
+/// Whole-name, prefix and substring matches rank above scattered subsequence
+/// matches such as "gta" for "Grand Theft Auto".
+pub fn fuzzy_score(query: &str, name: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    let name = name.to_lowercase();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+    if name == query {
+        return Some(1000);
+    }
+    if name.starts_with(&query) {
+        return Some(800);
+    }
+    if name.contains(&query) {
+        return Some(600);
+    }
+
+    // Subsequence match, rewarding consecutive characters and word starts
+    let mut score = 0;
+    let mut name_chars = name.chars();
+    let mut previous: Option<char> = None;
+    let mut last_matched = false;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        loop {
+            let name_char = name_chars.next()?;
+            let at_word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+            previous = Some(name_char);
+
+            if name_char == query_char {
+                score += match (last_matched, at_word_start) {
+                    (true, _) => 10,
+                    (false, true) => 8,
+                    (false, false) => 1,
+                };
+                last_matched = true;
+                break;
+            }
+            last_matched = false;
+        }
+    }
+
+    Some(score)
+}
+
+/// Apply a query to a list of applications. `patched_ids` are the applications
+/// with applied detection patches.
+pub fn run_query(
+    apps: &[GHUBApp],
+    query: &ApplicationQuery,
+    patched_ids: &HashSet<String>,
+) -> ApplicationQueryResult {
+    let search = query.search.as_deref().filter(|s| !s.trim().is_empty());
+
+    let mut matches: Vec<(u32, &GHUBApp)> = apps
+        .iter()
+        .filter(|app| query.has_detections.iter().all(|t| app.has_detection_type(*t)))
+        .filter(|app| !query.missing_detections.iter().any(|t| app.has_detection_type(*t)))
+        .filter(|app| {
+            query
+                .patched
+                .is_none_or(|patched| patched_ids.contains(&app.application_id) == patched)
+        })
+        .filter(|app| {
+            query
+                .has_poster
+                .is_none_or(|has_poster| app.poster_url.trim().is_empty() != has_poster)
+        })
+        .filter_map(|app| match search {
+            Some(search) => fuzzy_score(search, &app.name).map(|score| (score, app)),
+            None => Some((0, app)),
+        })
+        .collect();
+
+    let by_name = |a: &GHUBApp, b: &GHUBApp| a.name.to_lowercase().cmp(&b.name.to_lowercase());
+    matches.sort_by(|(score_a, a), (score_b, b)| {
+        let ordering = match query.sort_by {
+            SortField::Relevance => score_b.cmp(score_a).then_with(|| by_name(a, b)),
+            SortField::Name => by_name(a, b),
+            SortField::ApplicationId => a.application_id.cmp(&b.application_id),
+            SortField::DetectionCount => a.detection.len().cmp(&b.detection.len()).then_with(|| by_name(a, b)),
+            SortField::Version => a.version.cmp(&b.version).then_with(|| by_name(a, b)),
+        };
+        if query.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    let total = matches.len();
+    let items = matches
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .map(|(_, app)| app.clone())
+        .collect();
+
+    ApplicationQueryResult {
+        items,
+        total,
+        offset: query.offset,
+    }
+}
+
+/// Search, filter, sort and page the in-memory application list
+#[tauri::command]
+pub async fn query_applications(
+    app_handle: AppHandle,
+    query: ApplicationQuery,
+) -> Result<ApplicationQueryResult, String> {
+    let apps = get_stored_applications(&app_handle)?;
+
+    // Only read applied_patches.json when the query needs it
+    let patched_ids = if query.patched.is_some() {
+        load_applied_patches(&app_handle)?
+            .per_game_overrides
+            .into_iter()
+            .map(|o| o.application_id)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    Ok(run_query(&apps, &query, &patched_ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn apps() -> Vec<GHUBApp> {
+        [
+            json!({ "applicationId": "gta", "name": "Grand Theft Auto V", "posterUrl": "file://gta.jpg",
+                    "detection": [{ "steam": { "appId": "271590" } }, { "epicGames": { "appName": "9d2d" } }] }),
+            json!({ "applicationId": "tf2", "name": "Team Fortress 2",
+                    "detection": [{ "steam": { "appId": "440" } }] }),
+            json!({ "applicationId": "fn", "name": "Fortnite", "posterUrl": "file://fn.jpg",
+                    "detection": [{ "epicGames": { "appName": "Fortnite" } }] }),
+        ]
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect()
+    }
+
+    fn ids(result: &ApplicationQueryResult) -> Vec<&str> {
+        result.items.iter().map(|a| a.application_id.as_str()).collect()
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_closer_matches() {
+        assert!(fuzzy_score("fort", "Fortnite") > fuzzy_score("fort", "Team Fortress 2"));
+        assert!(fuzzy_score("gta", "Grand Theft Auto V").is_some());
+        assert_eq!(fuzzy_score("xyz", "Fortnite"), None);
+    }
+
+    #[test]
+    fn search_ranks_by_relevance() {
+        let query = ApplicationQuery {
+            search: Some("fort".to_string()),
+            ..Default::default()
+        };
+
+        let result = run_query(&apps(), &query, &HashSet::new());
+
+        assert_eq!(ids(&result), vec!["fn", "tf2"]);
+    }
+
+    #[test]
+    fn filters_by_detection_types_patch_status_and_poster() {
+        let has_steam_no_epic = ApplicationQuery {
+            has_detections: vec![DetectionType::Steam],
+            missing_detections: vec![DetectionType::EpicGames],
+            ..Default::default()
+        };
+        assert_eq!(ids(&run_query(&apps(), &has_steam_no_epic, &HashSet::new())), vec!["tf2"]);
+
+        let patched: HashSet<String> = ["gta".to_string()].into();
+        let unpatched_with_poster = ApplicationQuery {
+            patched: Some(false),
+            has_poster: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(ids(&run_query(&apps(), &unpatched_with_poster, &patched)), vec!["fn"]);
+    }
+
+    #[test]
+    fn sorts_and_pages() {
+        let query = ApplicationQuery {
+            sort_by: SortField::DetectionCount,
+            descending: true,
+            offset: 1,
+            limit: Some(1),
+            ..Default::default()
+        };
+
+        let result = run_query(&apps(), &query, &HashSet::new());
+
+        assert_eq!(result.total, 3);
+        assert_eq!(ids(&result), vec!["tf2"]);
+    }
+}
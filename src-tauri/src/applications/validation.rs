@@ -1,110 +1,317 @@
+use crate::applications::applications_json::parse_applications_json;
+use crate::applications::ghub_version::{read_current_json, read_version_json};
 use crate::core::constants::{STORE_KEY_DATA_PATH, STORE_FILENAME};
-use crate::applications::paths::{
-    get_applications_json_path, get_current_json_path, get_images_dir_path, get_version_json_path,
-};
-use serde_json::Value;
+use serde::Serialize;
 use std::fs;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
-#[derive(serde::Serialize)]
-pub struct PathValidationResult {
-    pub data_path_exists: bool,
-    pub applications_json_exists: bool,
-    pub current_json_exists: bool,
-    pub version_json_exists: bool,
-    pub build_id: Option<String>,
-    pub images_dir_exists: bool,
+/// The files and directories g-hauler relies on inside the G HUB data directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckedPath {
+    DataDir,
+    CurrentJson,
+    VersionJson,
+    BuildDir,
+    ApplicationsJson,
+    ImagesDir,
 }
 
-#[tauri::command]
-pub async fn validate_paths(app_handle: AppHandle) -> PathValidationResult {
-    let store = match app_handle.store(STORE_FILENAME) {
-        Ok(store) => store,
-        Err(_) => {
-            return PathValidationResult {
-                data_path_exists: false,
-                applications_json_exists: false,
-                current_json_exists: false,
-                version_json_exists: false,
-                build_id: None,
-                images_dir_exists: false,
-            };
+impl CheckedPath {
+    fn label(self) -> &'static str {
+        match self {
+            CheckedPath::DataDir => "G HUB data directory",
+            CheckedPath::CurrentJson => "current.json",
+            CheckedPath::VersionJson => "version.json",
+            CheckedPath::BuildDir => "build directory",
+            CheckedPath::ApplicationsJson => "applications.json",
+            CheckedPath::ImagesDir => "images directory",
         }
-    };
+    }
+
+    fn is_dir(self) -> bool {
+        matches!(self, CheckedPath::DataDir | CheckedPath::BuildDir | CheckedPath::ImagesDir)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum ParseStatus {
+    Ok,
+    Failed { error: String },
+}
+
+/// Result of checking one path
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathCheck {
+    pub name: CheckedPath,
+    pub label: String,
+    /// `None` when it can't be known yet, e.g. the build directory without a build id
+    pub path: Option<PathBuf>,
+    pub exists: bool,
+    pub readable: bool,
+    /// Only checked where g-hauler writes
+    pub writable: Option<bool>,
+    /// Only for JSON files
+    pub parse_status: Option<ParseStatus>,
+    /// Exists, is readable and parses
+    pub ok: bool,
+    /// What the user can do about a failure
+    pub hint: Option<String>,
+}
+
+/// Everything `validate_paths` found out about the configured data directory
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathDiagnostics {
+    pub data_path: Option<PathBuf>,
+    pub build_id: Option<String>,
+    pub checks: Vec<PathCheck>,
+    /// All checks passed; g-hauler can load applications
+    pub is_valid: bool,
+    /// applications.json and its directory can be written
+    pub can_save: bool,
+}
+
+impl PathDiagnostics {
+    pub fn check(&self, name: CheckedPath) -> Option<&PathCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
 
-    let data_path = match store
-        .get(STORE_KEY_DATA_PATH)
-        .and_then(|v| v.as_str().map(|s| s.to_string()))
-    {
-        Some(path) => path,
-        None => {
-            return PathValidationResult {
-                data_path_exists: false,
-                applications_json_exists: false,
-                current_json_exists: false,
-                version_json_exists: false,
-                build_id: None,
-                images_dir_exists: false,
-            };
+    fn not_configured() -> Self {
+        PathDiagnostics {
+            checks: vec![PathCheck {
+                name: CheckedPath::DataDir,
+                label: CheckedPath::DataDir.label().to_string(),
+                path: None,
+                exists: false,
+                readable: false,
+                writable: None,
+                parse_status: None,
+                ok: false,
+                hint: Some("Choose the G HUB data directory in settings".to_string()),
+            }],
+            ..Default::default()
         }
-    };
+    }
+}
 
-    let data_path_exists = fs::metadata(&data_path).is_ok();
+fn is_readable(path: &Path, is_dir: bool) -> bool {
+    if is_dir {
+        fs::read_dir(path).is_ok()
+    } else {
+        fs::File::open(path).is_ok()
+    }
+}
 
-    let current_json_path = get_current_json_path(&app_handle);
-    let version_json_path = get_version_json_path(&app_handle);
+/// Whether files can be created in `dir` or `file` can be opened for writing.
+/// Nothing is modified: files are opened without truncating and the directory
+/// probe is removed again.
+fn is_writable(path: &Path, is_dir: bool) -> bool {
+    if !is_dir {
+        return fs::OpenOptions::new().write(true).open(path).is_ok();
+    }
 
-    let current_json_exists = current_json_path
-        .as_ref()
-        .map(|p| fs::metadata(p).is_ok())
-        .unwrap_or(false);
+    let probe = path.join(format!(".g-hauler-write-test-{}", std::process::id()));
+    match fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
 
-    let version_json_exists = version_json_path
+fn failure_hint(name: CheckedPath, check: &PathCheck) -> Option<String> {
+    let label = name.label();
+    if !check.exists {
+        return Some(match name {
+            CheckedPath::DataDir => {
+                "Install G HUB or choose its data directory (normally C:\\ProgramData\\LGHUB on Windows, /Users/Shared/LGHUB on macOS)".to_string()
+            }
+            CheckedPath::CurrentJson | CheckedPath::VersionJson => {
+                format!("{} is missing; start G HUB once so it finishes installing", label)
+            }
+            _ => format!("The {} is missing; G HUB may be mid-update, restart it and try again", label),
+        });
+    }
+    if !check.readable {
+        return Some(format!("Permission denied reading the {}; check the folder's permissions", label));
+    }
+    if let Some(ParseStatus::Failed { .. }) = check.parse_status {
+        return Some(format!("{} is not valid JSON; G HUB may be writing it, wait and try again", label));
+    }
+    if check.writable == Some(false) {
+        return Some(format!(
+            "No write access to the {}; run g-hauler as administrator or give your user write permission",
+            label
+        ));
+    }
+    None
+}
+
+/// Parses a JSON file, for `PathCheck::parse_status`
+type ParseFn = fn(&Path) -> Result<(), String>;
+
+fn check_path(
+    name: CheckedPath,
+    path: Option<PathBuf>,
+    check_writable: bool,
+    parse: Option<ParseFn>,
+) -> PathCheck {
+    let is_dir = name.is_dir();
+    let exists = path
         .as_ref()
-        .map(|p| fs::metadata(p).is_ok())
-        .unwrap_or(false);
-
-    let build_id = if current_json_exists {
-        current_json_path
-            .and_then(|path| fs::read_to_string(&path).ok())
-            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
-            .and_then(|json| {
-                json.get("buildId")
-                    .and_then(|v| v.as_str().map(|s| s.to_string()))
-            })
-    } else {
-        None
+        .and_then(|p| fs::metadata(p).ok())
+        .is_some_and(|m| m.is_dir() == is_dir);
+    let readable = exists && path.as_deref().is_some_and(|p| is_readable(p, is_dir));
+    let writable = (check_writable && exists).then(|| path.as_deref().is_some_and(|p| is_writable(p, is_dir)));
+    let parse_status = match (parse, readable, path.as_deref()) {
+        (Some(parse), true, Some(p)) => Some(match parse(p) {
+            Ok(()) => ParseStatus::Ok,
+            Err(error) => ParseStatus::Failed { error },
+        }),
+        _ => None,
     };
 
-    let applications_json_exists = if let Some(ref build_id) = build_id {
-        if let Some(applications_json_path) = get_applications_json_path(&app_handle, build_id) {
-            fs::metadata(&applications_json_path).is_ok()
-        } else {
-            false
-        }
-    } else {
-        false
+    let mut check = PathCheck {
+        name,
+        label: name.label().to_string(),
+        path,
+        exists,
+        readable,
+        writable,
+        ok: readable && !matches!(parse_status, Some(ParseStatus::Failed { .. })),
+        parse_status,
+        hint: None,
     };
+    check.hint = failure_hint(name, &check);
+    check
+}
+
+/// Check the layout of a G HUB data directory
+pub fn inspect_data_path(data_path: &Path) -> PathDiagnostics {
+    let current_json_path = data_path.join("current.json");
+    let build_id = read_current_json(&current_json_path).ok().map(|c| c.build_id);
+    let build_dir = build_id
+        .as_ref()
+        .map(|build_id| data_path.join("depots").join(build_id));
+    let applications_json_path = build_dir
+        .as_ref()
+        .map(|dir| dir.join("core/LGHUB/data/applications.json"));
+
+    let mut checks = vec![
+        check_path(CheckedPath::DataDir, Some(data_path.to_path_buf()), false, None),
+        check_path(
+            CheckedPath::CurrentJson,
+            Some(current_json_path),
+            false,
+            Some(|p| read_current_json(p).map(|_| ())),
+        ),
+        check_path(
+            CheckedPath::VersionJson,
+            Some(data_path.join("version.json")),
+            false,
+            Some(|p| read_version_json(p).map(|_| ())),
+        ),
+        check_path(CheckedPath::BuildDir, build_dir.clone(), false, None),
+        check_path(
+            CheckedPath::ApplicationsJson,
+            applications_json_path.clone(),
+            true,
+            Some(|p| {
+                let content = fs::read_to_string(p).map_err(|e| format!("Failed to read applications.json: {}", e))?;
+                parse_applications_json(&content).map(|_| ())
+            }),
+        ),
+        check_path(CheckedPath::ImagesDir, build_dir.map(|dir| dir.join("core_apps/images")), true, None),
+    ];
 
-    let images_dir_exists = if let Some(ref build_id) = build_id {
-        if let Some(images_dir_path) = get_images_dir_path(&app_handle, build_id) {
-            fs::metadata(&images_dir_path)
-                .map(|m| m.is_dir())
-                .unwrap_or(false)
-        } else {
-            false
+    // Saving writes a temporary file next to applications.json, so its directory must be writable too
+    let data_dir_writable = applications_json_path
+        .as_deref()
+        .and_then(Path::parent)
+        .is_some_and(|dir| is_writable(dir, true));
+    if let Some(check) = checks.iter_mut().find(|c| c.name == CheckedPath::ApplicationsJson) {
+        if check.exists && !data_dir_writable {
+            check.writable = Some(false);
+            check.hint = failure_hint(CheckedPath::ApplicationsJson, check);
         }
-    } else {
-        false
-    };
+    }
+
+    let is_valid = checks.iter().all(|c| c.ok);
+    let can_save = checks
+        .iter()
+        .find(|c| c.name == CheckedPath::ApplicationsJson)
+        .is_some_and(|c| c.ok && c.writable == Some(true));
 
-    PathValidationResult {
-        data_path_exists,
-        applications_json_exists,
-        current_json_exists,
-        version_json_exists,
+    PathDiagnostics {
+        data_path: Some(data_path.to_path_buf()),
         build_id,
-        images_dir_exists,
+        checks,
+        is_valid,
+        can_save,
+    }
+}
+
+#[tauri::command]
+pub async fn validate_paths(app_handle: AppHandle) -> PathDiagnostics {
+    let data_path = app_handle
+        .store(STORE_FILENAME)
+        .ok()
+        .and_then(|store| store.get(STORE_KEY_DATA_PATH))
+        .and_then(|v| v.as_str().map(|s| s.to_string()));
+
+    match data_path {
+        Some(data_path) => inspect_data_path(Path::new(&data_path)),
+        None => PathDiagnostics::not_configured(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_each_path_with_a_hint_for_failures() {
+        let dir = std::env::temp_dir().join(format!("g-hauler-diagnostics-{}", std::process::id()));
+        let data = dir.join("depots/1234/core/LGHUB/data");
+        fs::create_dir_all(&data).unwrap();
+        fs::create_dir_all(dir.join("depots/1234/core_apps/images")).unwrap();
+        fs::write(dir.join("current.json"), r#"{ "buildId": "1234" }"#).unwrap();
+        fs::write(dir.join("version.json"), "{ not json").unwrap();
+        fs::write(data.join("applications.json"), r#"{ "applications": [] }"#).unwrap();
+
+        let diagnostics = inspect_data_path(&dir);
+
+        assert_eq!(diagnostics.build_id.as_deref(), Some("1234"));
+        assert!(!diagnostics.is_valid);
+        let version = diagnostics.check(CheckedPath::VersionJson).unwrap();
+        assert!(version.exists && version.readable && !version.ok);
+        assert!(matches!(version.parse_status, Some(ParseStatus::Failed { .. })));
+        assert!(version.hint.is_some());
+
+        let applications = diagnostics.check(CheckedPath::ApplicationsJson).unwrap();
+        assert_eq!(applications.parse_status, Some(ParseStatus::Ok));
+        assert_eq!(applications.writable, Some(true));
+        assert!(applications.hint.is_none());
+        assert!(diagnostics.can_save);
+
+        fs::write(dir.join("version.json"), r#"{ "version": "2025.3.1" }"#).unwrap();
+        assert!(inspect_data_path(&dir).is_valid);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_data_directory_has_a_hint() {
+        let diagnostics = inspect_data_path(Path::new("/nonexistent/LGHUB"));
+
+        assert!(!diagnostics.is_valid);
+        assert!(diagnostics.check(CheckedPath::DataDir).unwrap().hint.is_some());
+        assert_eq!(diagnostics.check(CheckedPath::BuildDir).unwrap().path, None);
     }
 }
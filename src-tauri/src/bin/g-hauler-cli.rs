@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(g_hauler_lib::cli::run())
+}
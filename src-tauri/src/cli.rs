@@ -0,0 +1,328 @@
+use crate::applications::applications_json::load_and_store_applications;
+use crate::applications::backups::create_backup;
+use crate::applications::bundles::bundle_export;
+use crate::applications::ghub_version::restore_after_version_change;
+use crate::applications::models::{Detection, GHUBApp};
+use crate::applications::paths::get_build_id;
+use crate::applications::query::{query_applications, ApplicationQuery};
+use crate::core::state::get_stored_applications;
+use crate::core::store::initialize_store;
+use crate::game_detection::models::ScanOptions;
+use crate::game_detection::scanner::GameScanner;
+use crate::ghub_game_patches::applier::{apply_all_patches, apply_patches_for_game, preview_patches};
+use crate::ghub_game_patches::loader::load_detection_patches;
+use crate::websocket::client::WebSocketClient;
+use crate::websocket::commands::connect_and_listen;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+const USAGE: &str = "\
+Usage: g-hauler-cli <command> [options]
+
+Commands:
+  scan [--quick]                       Scan launchers for installed games
+  apps list [--search TEXT] [--patched | --unpatched]
+                                       List applications from applications.json
+  patches preview [APP_ID...]          Show detections the bundled patches would add
+  patches apply [APP_ID... | --saved]  Apply patches through G HUB; --saved reapplies
+                                       the patches chosen in the app and, after a
+                                       G HUB update, restores custom apps and posters
+  backup                               Back up applications.json
+  export <PATH> [APP_ID...]            Export applications and posters to a bundle
+  help                                 Show this message
+
+Uses the data path and settings of the desktop app. Results are printed as JSON
+on stdout, messages go to stderr. Exits with 1 on failure and 2 on bad usage.";
+
+/// Exit code for failed commands
+const EXIT_FAILURE: i32 = 1;
+/// Exit code for unknown commands or options
+const EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Scan { quick: bool },
+    AppsList { search: Option<String>, patched: Option<bool> },
+    PatchesPreview { application_ids: Vec<String> },
+    PatchesApply { application_ids: Vec<String>, saved: bool },
+    Backup,
+    Export { path: String, application_ids: Vec<String> },
+    Help,
+}
+
+/// One row of `apps list`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppSummary {
+    application_id: String,
+    name: String,
+    /// Detection keys as in applications.json, e.g. `steam`
+    detections: Vec<String>,
+}
+
+impl From<&GHUBApp> for AppSummary {
+    fn from(app: &GHUBApp) -> Self {
+        AppSummary {
+            application_id: app.application_id.clone(),
+            name: app.name.clone(),
+            detections: app
+                .detection
+                .iter()
+                .map(|d| match d {
+                    Detection::Unknown(unknown) => unknown.key().unwrap_or("unknown").to_string(),
+                    _ => d.detection_type().map(|t| t.key()).unwrap_or_default().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Application ids given as positional arguments; options are rejected
+fn application_ids(args: &[String]) -> Result<Vec<String>, String> {
+    match args.iter().find(|a| a.starts_with("--")) {
+        Some(option) => Err(format!("Unknown option '{}'", option)),
+        None => Ok(args.to_vec()),
+    }
+}
+
+/// Parse the arguments after the program name
+pub fn parse_args(args: &[String]) -> Result<CliCommand, String> {
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        [] | ["help"] | ["--help"] | ["-h"] => Ok(CliCommand::Help),
+        ["scan"] => Ok(CliCommand::Scan { quick: false }),
+        ["scan", "--quick"] => Ok(CliCommand::Scan { quick: true }),
+        ["apps", "list", options @ ..] => {
+            let mut search = None;
+            let mut patched = None;
+            let mut options = options.iter();
+            while let Some(option) = options.next() {
+                match *option {
+                    "--search" => {
+                        let text = options.next().ok_or("--search needs a value")?;
+                        search = Some(text.to_string());
+                    }
+                    "--patched" => patched = Some(true),
+                    "--unpatched" => patched = Some(false),
+                    other => return Err(format!("Unknown option '{}'", other)),
+                }
+            }
+            Ok(CliCommand::AppsList { search, patched })
+        }
+        ["patches", "preview", ..] => Ok(CliCommand::PatchesPreview {
+            application_ids: application_ids(&args[2..])?,
+        }),
+        ["patches", "apply", "--saved"] => Ok(CliCommand::PatchesApply {
+            application_ids: Vec::new(),
+            saved: true,
+        }),
+        ["patches", "apply", ..] => Ok(CliCommand::PatchesApply {
+            application_ids: application_ids(&args[2..])?,
+            saved: false,
+        }),
+        ["backup"] => Ok(CliCommand::Backup),
+        ["export", path, ..] if !path.starts_with("--") => Ok(CliCommand::Export {
+            path: path.to_string(),
+            application_ids: application_ids(&args[2..])?,
+        }),
+        ["export"] => Err("export needs a file path".to_string()),
+        _ => Err(format!("Unknown command '{}'", args.join(" "))),
+    }
+}
+
+/// Read the store and load applications.json like the app does on startup
+async fn load_applications(app_handle: &AppHandle) -> Result<(), String> {
+    initialize_store(app_handle)
+        .await
+        .map_err(|e| format!("Failed to initialize store: {}", e))?;
+    let build_id = get_build_id(app_handle)
+        .ok_or("Failed to get build_id; check the G HUB data path in the app's settings")?;
+    load_and_store_applications(app_handle, &build_id)?;
+    Ok(())
+}
+
+async fn connect_to_ghub(app_handle: &AppHandle) -> Result<Arc<WebSocketClient>, String> {
+    let ws_client = Arc::new(WebSocketClient::new(app_handle.clone()));
+    connect_and_listen(app_handle, &ws_client, None)
+        .await
+        .map_err(|e| format!("Failed to connect to G HUB: {}", e))?;
+    Ok(ws_client)
+}
+
+async fn apply_patches(
+    app_handle: &AppHandle,
+    ws_client: &Arc<WebSocketClient>,
+    application_ids: &[String],
+    saved: bool,
+) -> Result<Value, String> {
+    if saved {
+        // The same routine the app runs on start, so custom apps and posters
+        // are restored too if G HUB was updated
+        let restore = restore_after_version_change(app_handle, ws_client, true).await?;
+        if !restore.patches_reapplied {
+            return Err(restore.warnings.join("; "));
+        }
+        return to_json(restore);
+    }
+    if application_ids.is_empty() {
+        return to_json(apply_all_patches(app_handle, ws_client).await?);
+    }
+
+    let patches_data = load_detection_patches(app_handle)?;
+    let mut applied = Vec::new();
+    for application_id in application_ids {
+        let detections = apply_patches_for_game(app_handle, ws_client, &patches_data, application_id).await?;
+        applied.push(json!({
+            "applicationId": application_id,
+            "appliedDetections": detections,
+        }));
+    }
+    Ok(Value::Array(applied))
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("Failed to serialize output: {}", e))
+}
+
+async fn execute(app_handle: &AppHandle, command: CliCommand) -> Result<Value, String> {
+    match command {
+        CliCommand::Scan { quick } => {
+            let options = if quick { ScanOptions::quick() } else { ScanOptions::all() };
+            to_json(GameScanner::new(options).scan_installed_games().await?)
+        }
+        CliCommand::AppsList { search, patched } => {
+            load_applications(app_handle).await?;
+            let query = ApplicationQuery {
+                search,
+                patched,
+                ..Default::default()
+            };
+            let result = query_applications(app_handle.clone(), query).await?;
+            to_json(result.items.iter().map(AppSummary::from).collect::<Vec<_>>())
+        }
+        CliCommand::PatchesPreview { application_ids } => {
+            load_applications(app_handle).await?;
+            let patches_data = load_detection_patches(app_handle)?;
+            let apps = get_stored_applications(app_handle)?;
+            to_json(preview_patches(&apps, &patches_data, &application_ids))
+        }
+        CliCommand::PatchesApply { application_ids, saved } => {
+            load_applications(app_handle).await?;
+            let ws_client = connect_to_ghub(app_handle).await?;
+            let result = apply_patches(app_handle, &ws_client, &application_ids, saved).await;
+            let _ = ws_client.disconnect().await;
+            result
+        }
+        CliCommand::Backup => {
+            initialize_store(app_handle)
+                .await
+                .map_err(|e| format!("Failed to initialize store: {}", e))?;
+            let build_id = get_build_id(app_handle).ok_or("Failed to get build_id")?;
+            let backup = create_backup(app_handle, &build_id)?
+                .ok_or("There is no applications.json to back up")?;
+            to_json(backup)
+        }
+        CliCommand::Export { path, application_ids } => {
+            load_applications(app_handle).await?;
+            let application_ids = if application_ids.is_empty() {
+                get_stored_applications(app_handle)?
+                    .into_iter()
+                    .map(|app| app.application_id)
+                    .collect()
+            } else {
+                application_ids
+            };
+            to_json(bundle_export(app_handle.clone(), application_ids, path).await?)
+        }
+        CliCommand::Help => Ok(Value::Null),
+    }
+}
+
+/// Run the CLI with the process arguments and return its exit code
+pub fn run() -> i32 {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(CliCommand::Help) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let app = match crate::build_headless_app() {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_FAILURE;
+        }
+    };
+
+    let result = tauri::async_runtime::block_on(execute(app.handle(), command)).and_then(|value| {
+        serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize output: {}", e))
+    });
+    match result {
+        Ok(output) => {
+            println!("{}", output);
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<CliCommand, String> {
+        parse_args(&args.split_whitespace().map(String::from).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn parses_subcommands_and_options() {
+        assert_eq!(parse(""), Ok(CliCommand::Help));
+        assert_eq!(parse("scan --quick"), Ok(CliCommand::Scan { quick: true }));
+        assert_eq!(
+            parse("apps list --search gta --unpatched"),
+            Ok(CliCommand::AppsList {
+                search: Some("gta".to_string()),
+                patched: Some(false),
+            })
+        );
+        assert_eq!(
+            parse("patches apply a b"),
+            Ok(CliCommand::PatchesApply {
+                application_ids: vec!["a".to_string(), "b".to_string()],
+                saved: false,
+            })
+        );
+        assert_eq!(
+            parse("patches apply --saved"),
+            Ok(CliCommand::PatchesApply { application_ids: Vec::new(), saved: true })
+        );
+        assert_eq!(
+            parse("export out.json a"),
+            Ok(CliCommand::Export {
+                path: "out.json".to_string(),
+                application_ids: vec!["a".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_options() {
+        assert!(parse("apps remove").is_err());
+        assert!(parse("apps list --search").is_err());
+        assert!(parse("patches preview --all").is_err());
+        assert!(parse("patches apply a --saved").is_err());
+        assert!(parse("export").is_err());
+    }
+}
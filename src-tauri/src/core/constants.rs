@@ -1,16 +1,38 @@
 // Store keys
 pub const STORE_KEY_DATA_PATH: &str = "lghub_data_path";
 pub const STORE_KEY_LAST_GHUB_VERSION: &str = "last_ghub_version";
+pub const STORE_KEY_LAST_GHUB_CHANNEL: &str = "last_ghub_channel";
 pub const STORE_KEY_AUTOSTART: &str = "autostart";
 pub const STORE_KEY_MINIMIZE_TO_TRAY: &str = "minimize_to_tray";
 pub const STORE_KEY_DEVELOPER_MODE: &str = "developer_mode";
+pub const STORE_KEY_GHUB_WEBSOCKET_URI: &str = "ghub_websocket_uri";
+pub const STORE_KEY_GHUB_CONNECT_TIMEOUT_MS: &str = "ghub_connect_timeout_ms";
+pub const STORE_KEY_GHUB_AUTO_CONNECT: &str = "ghub_auto_connect";
+pub const STORE_KEY_GHUB_RECONNECT_POLICY: &str = "ghub_reconnect_policy";
+pub const STORE_KEY_GHUB_RECONNECT_MAX_ATTEMPTS: &str = "ghub_reconnect_max_attempts";
+pub const STORE_KEY_BACKUP_RETENTION: &str = "applications_backup_retention";
 
 // Store constants
 pub const STORE_FILENAME: &str = "settings.json";
 pub const APPLIED_PATCHES_FILENAME: &str = "applied_patches.json";
+pub const CUSTOM_APPS_FILENAME: &str = "custom_apps.json";
+pub const BACKUPS_DIRNAME: &str = "backups";
+pub const POSTER_OVERRIDES_FILENAME: &str = "poster_overrides.json";
+pub const POSTER_OVERRIDES_DIRNAME: &str = "posters";
+pub const DEFAULT_BACKUP_RETENTION: u32 = 10;
+pub const EDIT_JOURNAL_CAPACITY: usize = 200;
 
 // File paths
 pub const LGHUB_DEFAULT_DATA_PATH: &str = r"C:\ProgramData\LGHUB";
+pub const LGHUB_DATA_PATH_ENV: &str = "LGHUB_DATA_PATH";
 
 // WebSocket configuration
 pub const DEFAULT_WEBSOCKET_URI: &str = "ws://localhost:9010";
+pub const DEFAULT_WEBSOCKET_PORT: u16 = 9010;
+pub const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5000;
+pub const DEFAULT_RECONNECT_MAX_ATTEMPTS: u32 = 5;
+pub const WEBSOCKET_LOG_CAPACITY: usize = 1000;
+/// Longer frames are cut when logged; application lists can be megabytes
+pub const WEBSOCKET_LOG_MAX_FRAME_BYTES: usize = 4096;
+/// Incoming messages buffered for callers waiting on a response
+pub const WEBSOCKET_RESPONSE_BUFFER: usize = 64;
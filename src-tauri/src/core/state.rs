@@ -1,17 +1,21 @@
 use std::sync::Mutex;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Manager, Runtime, State};
 
-use crate::applications::models::GHUBApp;
+use crate::applications::journal::EditJournal;
+use crate::applications::models::{GHUBApp, UnparsedApplication};
 use crate::settings::models::{SettingsState};
 use crate::settings::state as settings_state;
 
 pub struct AppState {
     pub applications: Mutex<Vec<GHUBApp>>,
+    /// Entries from applications.json that didn't parse, written back as-is on save
+    pub unparsed_applications: Mutex<Vec<UnparsedApplication>>,
+    pub edit_journal: Mutex<EditJournal>,
     pub settings_state: Mutex<SettingsState>,
 }
 
-pub fn store_applications_in_manager(
-    app_handle: &AppHandle,
+pub fn store_applications_in_manager<R: Runtime>(
+    app_handle: &AppHandle<R>,
     applications: &[GHUBApp],
 ) -> Result<(), String> {
     let state: State<AppState> = app_handle.state();
@@ -23,7 +27,7 @@ pub fn store_applications_in_manager(
     Ok(())
 }
 
-pub fn get_stored_applications(app_handle: &AppHandle) -> Result<Vec<GHUBApp>, String> {
+pub fn get_stored_applications<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Vec<GHUBApp>, String> {
     let state: State<AppState> = app_handle.state();
     let apps = state
         .applications
@@ -32,6 +36,29 @@ pub fn get_stored_applications(app_handle: &AppHandle) -> Result<Vec<GHUBApp>, S
     Ok(apps.clone())
 }
 
+pub fn store_unparsed_applications<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    unparsed: &[UnparsedApplication],
+) -> Result<(), String> {
+    let state: State<AppState> = app_handle.state();
+    let mut stored = state
+        .unparsed_applications
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on unparsed applications: {}", e))?;
+    *stored = unparsed.to_vec();
+    Ok(())
+}
+
+pub fn get_unparsed_applications<R: Runtime>(
+    app_handle: &AppHandle<R>,
+) -> Result<Vec<UnparsedApplication>, String> {
+    let state: State<AppState> = app_handle.state();
+    let stored = state
+        .unparsed_applications
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on unparsed applications: {}", e))?;
+    Ok(stored.clone())
+}
 
 pub fn refresh_settings_state(app_handle: &AppHandle) -> Result<(), String> {
     let built = settings_state::build_state(app_handle);
@@ -1,5 +1,8 @@
 use crate::core::constants::{LGHUB_DEFAULT_DATA_PATH, STORE_FILENAME, STORE_KEY_AUTOSTART, STORE_KEY_DATA_PATH, STORE_KEY_LAST_GHUB_VERSION};
+use crate::applications::discovery::{best_data_path, discover_data_paths};
+use crate::applications::validation::inspect_data_path;
 use serde_json::Value;
+use std::path::Path;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
@@ -14,7 +17,7 @@ pub async fn initialize_store(
 
     if changed {
         store.save()?;
-        println!("Store initialized with default values");
+        eprintln!("Store initialized with default values");
     }
 
     Ok(())
@@ -23,9 +26,25 @@ pub async fn initialize_store(
 fn set_store_defaults(store: &tauri_plugin_store::Store<tauri::Wry>) -> bool {
     let mut changed = false;
 
-    if store.get(STORE_KEY_DATA_PATH).is_none() {
-        store.set(STORE_KEY_DATA_PATH, json!(LGHUB_DEFAULT_DATA_PATH));
-        changed = true;
+    // Pick the best G HUB data directory on first run, and again while the
+    // untouched Windows default doesn't exist (e.g. on macOS)
+    let stored_path = store
+        .get(STORE_KEY_DATA_PATH)
+        .and_then(|v| v.as_str().map(String::from));
+    let needs_discovery = match stored_path.as_deref() {
+        None => true,
+        Some(path) => path == LGHUB_DEFAULT_DATA_PATH && !inspect_data_path(Path::new(path)).is_valid,
+    };
+    if needs_discovery {
+        let candidates = discover_data_paths(None);
+        let data_path = best_data_path(&candidates)
+            .map(|c| c.path.to_string_lossy().to_string())
+            .unwrap_or_else(|| LGHUB_DEFAULT_DATA_PATH.to_string());
+        if stored_path.as_deref() != Some(data_path.as_str()) {
+            eprintln!("Using G HUB data directory: {}", data_path);
+            store.set(STORE_KEY_DATA_PATH, json!(data_path));
+            changed = true;
+        }
     }
 
     if store.get(STORE_KEY_LAST_GHUB_VERSION).is_none() {
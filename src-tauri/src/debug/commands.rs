@@ -1,6 +1,21 @@
-use tauri::{AppHandle, WebviewWindow};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State, WebviewWindow};
 use crate::core::store::get_store_key;
 use crate::core::constants::STORE_KEY_DEVELOPER_MODE;
+use crate::websocket::WebSocketClient;
+use crate::websocket::message_log::{MessageLogEntry, MessageLogFilter};
+
+fn require_developer_mode(app_handle: &AppHandle) -> Result<(), String> {
+    let is_dev_mode = get_store_key(app_handle, STORE_KEY_DEVELOPER_MODE)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if !is_dev_mode {
+        return Err("Developer mode is not enabled".to_string());
+    }
+    Ok(())
+}
 
 #[tauri::command]
 pub async fn is_developer_mode(app_handle: AppHandle) -> bool {
@@ -11,14 +26,7 @@ pub async fn is_developer_mode(app_handle: AppHandle) -> bool {
 
 #[tauri::command]
 pub async fn open_devtools(app_handle: AppHandle, window: WebviewWindow) -> Result<(), String> {
-    // Check if developer mode is enabled
-    let is_dev_mode = get_store_key(&app_handle, STORE_KEY_DEVELOPER_MODE)
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    if !is_dev_mode {
-        return Err("Developer mode is not enabled".to_string());
-    }
+    require_developer_mode(&app_handle)?;
 
     #[cfg(debug_assertions)]
     {
@@ -34,14 +42,7 @@ pub async fn open_devtools(app_handle: AppHandle, window: WebviewWindow) -> Resu
 
 #[tauri::command]
 pub async fn close_devtools(app_handle: AppHandle, window: WebviewWindow) -> Result<(), String> {
-    // Check if developer mode is enabled
-    let is_dev_mode = get_store_key(&app_handle, STORE_KEY_DEVELOPER_MODE)
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    if !is_dev_mode {
-        return Err("Developer mode is not enabled".to_string());
-    }
+    require_developer_mode(&app_handle)?;
 
     #[cfg(debug_assertions)]
     {
@@ -67,3 +68,75 @@ pub async fn is_devtools_open(window: WebviewWindow) -> bool {
         false
     }
 }
+
+/// Query recent WebSocket frames and connection events
+#[tauri::command]
+pub async fn get_websocket_log(
+    app_handle: AppHandle,
+    ws_client: State<'_, Arc<WebSocketClient>>,
+    filter: Option<MessageLogFilter>,
+) -> Result<Vec<MessageLogEntry>, String> {
+    require_developer_mode(&app_handle)?;
+
+    let log = ws_client
+        .message_log
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on message log: {}", e))?;
+    Ok(log.query(&filter.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub async fn clear_websocket_log(
+    app_handle: AppHandle,
+    ws_client: State<'_, Arc<WebSocketClient>>,
+) -> Result<(), String> {
+    require_developer_mode(&app_handle)?;
+
+    ws_client
+        .message_log
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on message log: {}", e))?
+        .clear();
+    Ok(())
+}
+
+/// Write the (optionally filtered) log as JSON to `path`, or to a timestamped
+/// file in the app log directory. Returns the path written.
+#[tauri::command]
+pub async fn export_websocket_log(
+    app_handle: AppHandle,
+    ws_client: State<'_, Arc<WebSocketClient>>,
+    path: Option<String>,
+    filter: Option<MessageLogFilter>,
+) -> Result<String, String> {
+    require_developer_mode(&app_handle)?;
+
+    let entries = ws_client
+        .message_log
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock on message log: {}", e))?
+        .query(&filter.unwrap_or_default());
+
+    let export_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let log_dir = app_handle
+                .path()
+                .app_log_dir()
+                .map_err(|e| format!("Failed to get app log directory: {}", e))?;
+            std::fs::create_dir_all(&log_dir)
+                .map_err(|e| format!("Failed to create app log directory: {}", e))?;
+            log_dir.join(format!(
+                "websocket-log-{}.json",
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            ))
+        }
+    };
+
+    let json_content = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize message log: {}", e))?;
+    std::fs::write(&export_path, json_content)
+        .map_err(|e| format!("Failed to write {}: {}", export_path.display(), e))?;
+
+    Ok(export_path.to_string_lossy().to_string())
+}
@@ -1,5 +1,8 @@
+use super::glob_detection::{evaluate_glob, GlobEvaluation};
 use super::models::{GameScanResult, ScanOptions};
 use super::scanner::GameScanner;
+use crate::applications::models::Detection;
+use crate::core::state::get_stored_applications;
 use tauri::AppHandle;
 
 /// Scan for all locally installed games
@@ -27,3 +30,36 @@ pub async fn full_scan_games(_app_handle: AppHandle) -> Result<GameScanResult, S
     let scanner = GameScanner::new(ScanOptions::all());
     scanner.scan_installed_games().await
 }
+
+/// Expand a glob pattern against the local filesystem, to test it before adding it as a detection
+#[tauri::command]
+pub async fn test_glob_detection(pattern: String) -> Result<GlobEvaluation, String> {
+    tokio::task::spawn_blocking(move || evaluate_glob(&pattern))
+        .await
+        .map_err(|e| format!("Glob evaluation failed: {}", e))
+}
+
+/// Evaluate every glob detection of an application
+#[tauri::command]
+pub async fn evaluate_application_globs(
+    app_handle: AppHandle,
+    application_id: String,
+) -> Result<Vec<GlobEvaluation>, String> {
+    let apps = get_stored_applications(&app_handle)?;
+    let app = apps
+        .into_iter()
+        .find(|a| a.application_id == application_id)
+        .ok_or_else(|| format!("Application with ID '{}' not found", application_id))?;
+
+    tokio::task::spawn_blocking(move || {
+        app.detection
+            .iter()
+            .filter_map(|d| match d {
+                Detection::Glob { glob } => Some(evaluate_glob(glob)),
+                _ => None,
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Glob evaluation failed: {}", e))
+}
@@ -0,0 +1,354 @@
+use crate::applications::models::{Detection, DetectionType, GHUBApp, WinRegistry};
+use crate::game_detection::glob_detection::evaluate_glob;
+use crate::core::state::get_stored_applications;
+use crate::game_detection::models::{GamePlatform, GameScanResult, ScanOptions};
+use crate::game_detection::scanner::GameScanner;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DetectionStatus {
+    /// G HUB would detect the game through this entry
+    Detected,
+    NotDetected,
+    /// Can't be checked on this OS, or the launcher couldn't be scanned
+    Unknown,
+    /// The entry itself is broken, e.g. an invalid glob
+    Invalid,
+}
+
+/// Outcome for one `Detection` entry of an application
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionCheck {
+    pub index: usize,
+    /// `None` for unknown detection types
+    pub detection_type: Option<DetectionType>,
+    pub status: DetectionStatus,
+    /// Why it was or wasn't detected
+    pub reason: String,
+    /// Install directory, executable or matched file backing a detection
+    pub evidence: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDetectionReport {
+    pub application_id: String,
+    pub name: String,
+    /// At least one detection fires
+    pub detected: bool,
+    pub checks: Vec<DetectionCheck>,
+}
+
+/// Status, reason and evidence of a single check
+type Outcome = (DetectionStatus, String, Option<PathBuf>);
+
+fn check(status: DetectionStatus, reason: impl Into<String>, evidence: Option<PathBuf>) -> Outcome {
+    (status, reason.into(), evidence)
+}
+
+/// Look a launcher id up in the scan results
+fn check_scanned(
+    scan: &GameScanResult,
+    platform_name: &str,
+    id: &str,
+    matches: impl Fn(&GamePlatform) -> bool,
+) -> Outcome {
+    if let Some(game) = scan.games.iter().find(|g| matches(&g.platform)) {
+        let evidence = game.install_path.clone().or_else(|| game.executable_path.clone());
+        return check(
+            DetectionStatus::Detected,
+            format!("{} reports '{}' as installed", platform_name, game.name),
+            evidence,
+        );
+    }
+
+    if scan.failed_platforms.iter().any(|p| p == platform_name) {
+        return check(DetectionStatus::Unknown, format!("{} could not be scanned", platform_name), None);
+    }
+    if !scan.scanned_platforms.iter().any(|p| p == platform_name) {
+        return check(DetectionStatus::Unknown, format!("{} was not scanned", platform_name), None);
+    }
+    check(
+        DetectionStatus::NotDetected,
+        format!("{} has no installed game with id '{}'", platform_name, id),
+        None,
+    )
+}
+
+/// Split `HKEY_LOCAL_MACHINE/SOFTWARE/Vendor` into the hive's full name and a
+/// backslash-separated subkey
+pub fn split_registry_path(registry_path: &str) -> Option<(&'static str, String)> {
+    let mut parts = registry_path.split(['/', '\\']).filter(|p| !p.is_empty());
+    let hive = match parts.next()?.to_ascii_uppercase().as_str() {
+        "HKEY_LOCAL_MACHINE" | "HKLM" => "HKEY_LOCAL_MACHINE",
+        "HKEY_CURRENT_USER" | "HKCU" => "HKEY_CURRENT_USER",
+        "HKEY_CLASSES_ROOT" | "HKCR" => "HKEY_CLASSES_ROOT",
+        "HKEY_USERS" | "HKU" => "HKEY_USERS",
+        "HKEY_CURRENT_CONFIG" | "HKCC" => "HKEY_CURRENT_CONFIG",
+        _ => return None,
+    };
+    Some((hive, parts.collect::<Vec<_>>().join("\\")))
+}
+
+/// Read the install directory from the registry and look for the executable in it
+#[cfg(target_os = "windows")]
+fn check_registry(detection: &WinRegistry) -> Outcome {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let Some((hive, subkey)) = split_registry_path(&detection.registry_path) else {
+        return check(DetectionStatus::Invalid, "Registry path has no known hive", None);
+    };
+    let root = RegKey::predef(match hive {
+        "HKEY_LOCAL_MACHINE" => HKEY_LOCAL_MACHINE,
+        "HKEY_CURRENT_USER" => HKEY_CURRENT_USER,
+        "HKEY_CLASSES_ROOT" => HKEY_CLASSES_ROOT,
+        "HKEY_USERS" => HKEY_USERS,
+        _ => HKEY_CURRENT_CONFIG,
+    });
+
+    let key = match root.open_subkey(&subkey) {
+        Ok(key) => key,
+        Err(_) => return check(DetectionStatus::NotDetected, format!("Registry key {}\\{} does not exist", hive, subkey), None),
+    };
+    let install_dir: String = match key.get_value(&detection.registry_key) {
+        Ok(value) => value,
+        Err(_) => {
+            return check(
+                DetectionStatus::NotDetected,
+                format!("Registry value '{}' is not set", detection.registry_key),
+                None,
+            )
+        }
+    };
+
+    check_executable(Path::new(&install_dir), &detection.executable)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn check_registry(_detection: &WinRegistry) -> Outcome {
+    check(DetectionStatus::Unknown, "Registry detections can only be checked on Windows", None)
+}
+
+/// G HUB matches the executable inside the directory the registry points at
+#[cfg(target_os = "windows")]
+fn check_executable(install_dir: &Path, executable: &str) -> Outcome {
+    let path = install_dir.join(executable.trim_start_matches(['/', '\\']));
+    if path.is_file() {
+        check(DetectionStatus::Detected, "Executable found in the registered install directory", Some(path))
+    } else {
+        check(
+            DetectionStatus::NotDetected,
+            format!("{} is not in {}", executable, install_dir.display()),
+            Some(install_dir.to_path_buf()),
+        )
+    }
+}
+
+fn check_osx_bundle(scan: &GameScanResult, bundle_id: &str, bundle_path: &str) -> Outcome {
+    let (status, reason, evidence) = check_scanned(scan, GamePlatform::OSX_BUNDLE_NAME, bundle_id, |p| {
+        matches!(p, GamePlatform::OsxBundle { bundle_id: id } if id == bundle_id)
+    });
+    if status == DetectionStatus::Detected {
+        return (status, reason, evidence);
+    }
+
+    // The scan only lists apps it thinks are games, so look at the bundle directly
+    if cfg!(target_os = "macos") && !bundle_path.is_empty() && Path::new(bundle_path).exists() {
+        return check(DetectionStatus::Detected, "Bundle exists at the given path", Some(PathBuf::from(bundle_path)));
+    }
+    (status, reason, evidence)
+}
+
+pub fn evaluate_detection(index: usize, detection: &Detection, scan: &GameScanResult) -> DetectionCheck {
+    let (status, reason, evidence) = match detection {
+        Detection::Steam { steam } => check_scanned(scan, GamePlatform::STEAM_NAME, &steam.app_id, |p| {
+            matches!(p, GamePlatform::Steam { app_id } if *app_id == steam.app_id)
+        }),
+        Detection::EpicGames { epic_games } => {
+            check_scanned(scan, GamePlatform::EPIC_GAMES_NAME, &epic_games.app_name, |p| {
+                matches!(p, GamePlatform::EpicGames { app_name } if app_name.eq_ignore_ascii_case(&epic_games.app_name))
+            })
+        }
+        Detection::Uplay { uplay } => check_scanned(scan, GamePlatform::UPLAY_NAME, &uplay.app_id, |p| {
+            matches!(p, GamePlatform::Uplay { app_id } if *app_id == uplay.app_id)
+        }),
+        Detection::GogGalaxy { gog_galaxy } => {
+            check_scanned(scan, GamePlatform::GOG_GALAXY_NAME, &gog_galaxy.product_id, |p| {
+                matches!(p, GamePlatform::GogGalaxy { product_id } if *product_id == gog_galaxy.product_id)
+            })
+        }
+        Detection::RiotGames { riot_games } => {
+            check_scanned(scan, GamePlatform::RIOT_GAMES_NAME, &riot_games.app_name, |p| {
+                matches!(p, GamePlatform::RiotGames { app_name } if app_name.eq_ignore_ascii_case(&riot_games.app_name))
+            })
+        }
+        Detection::OsxBundle { osx_bundle } => check_osx_bundle(scan, &osx_bundle.bundle_id, &osx_bundle.bundle_path),
+        Detection::WinRegistry { win_registry } => check_registry(win_registry),
+        Detection::Glob { glob } => {
+            let evaluation = evaluate_glob(glob);
+            match (&evaluation.error, evaluation.matches.first()) {
+                (Some(error), _) => check(DetectionStatus::Invalid, error.clone(), None),
+                (None, Some(path)) => check(
+                    DetectionStatus::Detected,
+                    format!("Glob matches {} file(s)", evaluation.matches.len()),
+                    Some(path.clone()),
+                ),
+                (None, None) if evaluation.incomplete => check(
+                    DetectionStatus::Unknown,
+                    format!("Stopped searching for {} before finding a match", evaluation.expanded_pattern),
+                    None,
+                ),
+                (None, None) => check(
+                    DetectionStatus::NotDetected,
+                    format!("No files match {}", evaluation.expanded_pattern),
+                    None,
+                ),
+            }
+        }
+        Detection::Unknown(unknown) => match (unknown.malformed_type(), unknown.key()) {
+            (Some(detection_type), _) => check(
+                DetectionStatus::Invalid,
+                format!("Malformed {} detection", detection_type.key()),
+                None,
+            ),
+            (None, Some(key)) => check(DetectionStatus::Unknown, format!("Unknown detection type '{}'", key), None),
+            (None, None) => check(DetectionStatus::Unknown, "Unknown detection type", None),
+        },
+    };
+
+    DetectionCheck {
+        index,
+        detection_type: detection.detection_type(),
+        status,
+        reason,
+        evidence,
+    }
+}
+
+/// Check every detection of an application against a scan of this machine
+pub fn evaluate_application(app: &GHUBApp, scan: &GameScanResult) -> AppDetectionReport {
+    let checks: Vec<DetectionCheck> = app
+        .detection
+        .iter()
+        .enumerate()
+        .map(|(index, detection)| evaluate_detection(index, detection, scan))
+        .collect();
+
+    AppDetectionReport {
+        application_id: app.application_id.clone(),
+        name: app.name.clone(),
+        detected: checks.iter().any(|c| c.status == DetectionStatus::Detected),
+        checks,
+    }
+}
+
+/// Scan this machine and report, per application, which detections G HUB would match
+#[tauri::command]
+pub async fn evaluate_app_detections(
+    app_handle: AppHandle,
+    application_ids: Option<Vec<String>>,
+    options: Option<ScanOptions>,
+) -> Result<Vec<AppDetectionReport>, String> {
+    let apps = get_stored_applications(&app_handle)?;
+    let scan = GameScanner::new(options.unwrap_or_default())
+        .scan_installed_games()
+        .await?;
+
+    // Glob and registry checks hit the filesystem, so keep them off the async runtime
+    tokio::task::spawn_blocking(move || {
+        apps.iter()
+            .filter(|app| {
+                application_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&app.application_id))
+            })
+            .map(|app| evaluate_application(app, &scan))
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Detection evaluation failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_detection::models::DetectedGame;
+    use serde_json::json;
+
+    fn scan() -> GameScanResult {
+        let mut scan = GameScanResult::new();
+        scan.add_games(
+            GamePlatform::STEAM_NAME.to_string(),
+            vec![DetectedGame::new(
+                "440".to_string(),
+                "Team Fortress 2".to_string(),
+                None,
+                Some(PathBuf::from("/games/tf2")),
+                GamePlatform::Steam { app_id: "440".to_string() },
+            )],
+        );
+        scan.add_games(GamePlatform::EPIC_GAMES_NAME.to_string(), Vec::new());
+        scan.mark_failed(GamePlatform::UPLAY_NAME.to_string(), "Uplay is broken".to_string());
+        scan
+    }
+
+    fn app(detection: serde_json::Value) -> GHUBApp {
+        serde_json::from_value(json!({ "applicationId": "a", "name": "Alpha", "detection": detection })).unwrap()
+    }
+
+    #[test]
+    fn matches_launcher_ids_against_the_scan() {
+        let report = evaluate_application(
+            &app(json!([
+                { "steam": { "appId": "440" } },
+                { "steam": { "appId": "570" } },
+                { "epicGames": { "appName": "Fortnite" } },
+                { "uplay": { "appId": "5" } },
+                { "gogGalaxy": { "productId": "1" } },
+            ])),
+            &scan(),
+        );
+
+        let statuses: Vec<DetectionStatus> = report.checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                DetectionStatus::Detected,
+                DetectionStatus::NotDetected,
+                DetectionStatus::NotDetected,
+                DetectionStatus::Unknown,
+                DetectionStatus::Unknown,
+            ]
+        );
+        assert!(report.detected);
+        assert_eq!(report.checks[0].evidence, Some(PathBuf::from("/games/tf2")));
+    }
+
+    #[test]
+    fn reports_invalid_globs_and_unknown_types() {
+        let report = evaluate_application(&app(json!([{ "glob": "/[broken" }, { "futureLauncher": {} }])), &scan());
+
+        assert!(!report.detected);
+        assert_eq!(report.checks[0].status, DetectionStatus::Invalid);
+        assert_eq!(report.checks[1].status, DetectionStatus::Unknown);
+        assert_eq!(report.checks[1].detection_type, None);
+    }
+
+    #[test]
+    fn splits_registry_paths() {
+        assert_eq!(
+            split_registry_path("HKEY_LOCAL_MACHINE/SOFTWARE/Vendor/Game"),
+            Some(("HKEY_LOCAL_MACHINE", "SOFTWARE\\Vendor\\Game".to_string()))
+        );
+        assert_eq!(
+            split_registry_path("hkcu\\Software\\Game"),
+            Some(("HKEY_CURRENT_USER", "Software\\Game".to_string()))
+        );
+        assert_eq!(split_registry_path("SOFTWARE/Game"), None);
+    }
+}
@@ -0,0 +1,243 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Stop expanding a glob after this many matches
+pub const MAX_GLOB_MATCHES: usize = 100;
+/// How many directories below its fixed prefix a `**` glob descends
+pub const MAX_GLOB_DEPTH: usize = 8;
+/// Stop expanding a glob after looking at this many filesystem entries
+pub const MAX_GLOB_ENTRIES: usize = 50_000;
+/// Stop expanding a glob after this long
+pub const GLOB_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// Result of expanding one glob detection against the filesystem
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobEvaluation {
+    /// The glob as written in the detection
+    pub pattern: String,
+    /// After expanding environment variables and `~`
+    pub expanded_pattern: String,
+    pub matches: Vec<PathBuf>,
+    /// More files matched than `MAX_GLOB_MATCHES`
+    pub truncated: bool,
+    /// The walk hit `MAX_GLOB_DEPTH`, `MAX_GLOB_ENTRIES` or `GLOB_TIME_LIMIT`,
+    /// so files further down may match too
+    pub incomplete: bool,
+    /// Set when the pattern is not a valid glob
+    pub error: Option<String>,
+}
+
+impl GlobEvaluation {
+    /// Whether G HUB would detect the game through this glob
+    pub fn is_match(&self) -> bool {
+        !self.matches.is_empty()
+    }
+}
+
+/// Expand `%VAR%` environment variables and a leading `~`, and use `/` as the
+/// separator so Windows paths work as glob patterns
+pub fn expand_glob_pattern(pattern: &str) -> String {
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut rest = pattern.trim();
+
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        expanded.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    expanded.push_str(rest);
+
+    if let Some(tail) = expanded.strip_prefix('~') {
+        let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+        if let Ok(home) = home {
+            if tail.is_empty() || tail.starts_with(['/', '\\']) {
+                expanded = format!("{}{}", home, tail);
+            }
+        }
+    }
+
+    expanded.replace('\\', "/")
+}
+
+/// Expand a G HUB glob detection and list the files it matches. Matching is
+/// case-insensitive like the Windows and macOS filesystems G HUB runs on.
+/// The walk is bounded, see `GlobEvaluation::incomplete`; it blocks, so call
+/// it from a blocking task.
+pub fn evaluate_glob(pattern: &str) -> GlobEvaluation {
+    let expanded_pattern = expand_glob_pattern(pattern);
+    let mut evaluation = GlobEvaluation {
+        pattern: pattern.to_string(),
+        expanded_pattern: expanded_pattern.clone(),
+        matches: Vec::new(),
+        truncated: false,
+        incomplete: false,
+        error: None,
+    };
+
+    let compiled = match glob::Pattern::new(&expanded_pattern) {
+        Ok(compiled) => compiled,
+        Err(e) => {
+            evaluation.error = Some(format!("Invalid glob pattern: {}", e));
+            return evaluation;
+        }
+    };
+
+    // Components before the first wildcard name a fixed directory to start from
+    let components: Vec<&str> = expanded_pattern.split('/').collect();
+    let fixed = components
+        .iter()
+        .position(|c| c.contains(['*', '?', '[']))
+        .unwrap_or(components.len());
+    let max_depth = if components[fixed..].contains(&"**") {
+        MAX_GLOB_DEPTH
+    } else {
+        components.len() - fixed
+    };
+
+    if fixed == components.len() {
+        let path = PathBuf::from(&expanded_pattern);
+        if path.exists() {
+            evaluation.matches.push(path);
+        }
+        return evaluation;
+    }
+
+    let mut walk = GlobWalk {
+        pattern: compiled,
+        deadline: Instant::now() + GLOB_TIME_LIMIT,
+        entries: 0,
+        stopped: false,
+        evaluation,
+    };
+    let base = components[..fixed].join("/");
+    let base = match base.as_str() {
+        // `/` for absolute Unix paths
+        "" if fixed > 0 => PathBuf::from("/"),
+        _ => PathBuf::from(base),
+    };
+    walk.visit(&base, 1, max_depth);
+    walk.evaluation
+}
+
+struct GlobWalk {
+    pattern: glob::Pattern,
+    deadline: Instant,
+    entries: usize,
+    /// Out of matches, entries or time; unlike the depth limit this ends the walk
+    stopped: bool,
+    evaluation: GlobEvaluation,
+}
+
+impl GlobWalk {
+    fn options() -> glob::MatchOptions {
+        glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        }
+    }
+
+    /// Match the entries of `dir`, `depth` levels below the fixed prefix, and descend
+    fn visit(&mut self, dir: &Path, depth: usize, max_depth: usize) {
+        let read_from = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        // Unreadable directories are skipped, as G HUB would
+        let Ok(entries) = std::fs::read_dir(read_from) else {
+            return;
+        };
+        let mut names: Vec<_> = entries.filter_map(Result::ok).map(|e| e.file_name()).collect();
+        names.sort();
+
+        for name in names {
+            if self.stopped {
+                return;
+            }
+            self.entries += 1;
+            if self.entries > MAX_GLOB_ENTRIES || Instant::now() > self.deadline {
+                self.evaluation.incomplete = true;
+                self.stopped = true;
+                return;
+            }
+
+            let path = dir.join(&name);
+            if self.pattern.matches_path_with(&path, Self::options()) {
+                if self.evaluation.matches.len() == MAX_GLOB_MATCHES {
+                    self.evaluation.truncated = true;
+                    self.stopped = true;
+                    return;
+                }
+                self.evaluation.matches.push(path.clone());
+            }
+
+            if path.is_dir() {
+                if depth < max_depth {
+                    self.visit(&path, depth + 1, max_depth);
+                } else if max_depth == MAX_GLOB_DEPTH {
+                    self.evaluation.incomplete = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn expands_environment_variables_and_separators() {
+        std::env::set_var("G_HAULER_GLOB_TEST", "C:\\Games");
+
+        assert_eq!(expand_glob_pattern("%G_HAULER_GLOB_TEST%\\Game\\*.exe"), "C:/Games/Game/*.exe");
+        assert_eq!(expand_glob_pattern("%NOT_SET_ANYWHERE%/x.exe"), "%NOT_SET_ANYWHERE%/x.exe");
+        assert_eq!(expand_glob_pattern("100% sure/x.exe"), "100% sure/x.exe");
+    }
+
+    #[test]
+    fn lists_matching_files() {
+        let dir = std::env::temp_dir().join(format!("g-hauler-glob-{}", std::process::id()));
+        fs::create_dir_all(dir.join("bin")).unwrap();
+        fs::write(dir.join("bin/Game.exe"), b"").unwrap();
+        fs::write(dir.join("bin/readme.txt"), b"").unwrap();
+        let root = dir.to_string_lossy().replace('\\', "/");
+
+        let evaluation = evaluate_glob(&format!("{}/**/game.EXE", root));
+        assert!(evaluation.is_match());
+        assert_eq!(evaluation.matches, vec![dir.join("bin/Game.exe")]);
+
+        assert!(!evaluate_glob(&format!("{}/*.exe", root)).is_match());
+        assert!(evaluate_glob(&format!("{}/[bin", root)).error.is_some());
+        assert_eq!(evaluate_glob(&format!("{}/bin/Game.exe", root)).matches.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn recursive_globs_stop_at_the_depth_limit() {
+        let dir = std::env::temp_dir().join(format!("g-hauler-glob-depth-{}", std::process::id()));
+        let mut deep = dir.clone();
+        for level in 0..MAX_GLOB_DEPTH + 2 {
+            deep = deep.join(format!("d{}", level));
+        }
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("game.exe"), b"").unwrap();
+        fs::write(dir.join("d0/game.exe"), b"").unwrap();
+        let root = dir.to_string_lossy().replace('\\', "/");
+
+        let evaluation = evaluate_glob(&format!("{}/**/game.exe", root));
+
+        assert_eq!(evaluation.matches, vec![dir.join("d0/game.exe")]);
+        assert!(evaluation.incomplete);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,6 @@
 pub mod commands;
+pub mod evaluator;
+pub mod glob_detection;
 pub mod models;
 pub mod scanner;
 pub mod platforms;
@@ -1,13 +1,14 @@
+use crate::applications::journal::{record_edit, EditSource};
 use crate::applications::models::{Detection, GHUBApp};
 use crate::core::state::AppState;
 use crate::ghub_game_patches::loader::{get_patches_for_game, load_detection_patches};
 use crate::ghub_game_patches::models::{
-    AppliedPatchesData, DetectionPatchesData, GamePatchOverride,
+    AppliedPatchesData, DetectionPatchesData, GamePatchOverride, PatchPreview,
 };
 use crate::ghub_game_patches::persistence::{load_applied_patches, save_applied_patches};
 use crate::websocket::client::{WebSocketClient, WebSocketMessage};
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Manager, Runtime, State};
 
 /// Get missing detection platforms for a specific game
 pub fn get_missing_detections(
@@ -30,6 +31,29 @@ pub fn get_missing_detections(
     missing
 }
 
+/// What applying the bundled patches would add, per game present in `apps`.
+/// An empty `application_ids` previews every game with patches.
+pub fn preview_patches(
+    apps: &[GHUBApp],
+    patches_data: &DetectionPatchesData,
+    application_ids: &[String],
+) -> Vec<PatchPreview> {
+    patches_data
+        .patches
+        .iter()
+        .filter(|p| application_ids.is_empty() || application_ids.contains(&p.application_id))
+        .filter_map(|game_patches| {
+            let app = apps.iter().find(|a| a.application_id == game_patches.application_id)?;
+            let missing_detections = get_missing_detections(app, &game_patches.detections);
+            (!missing_detections.is_empty()).then(|| PatchPreview {
+                application_id: game_patches.application_id.clone(),
+                game_name: game_patches.game_name.clone(),
+                missing_detections,
+            })
+        })
+        .collect()
+}
+
 /// Check if two Detection variants are of the same type (e.g., both Steam, both EpicGames, etc.)
 fn detection_types_match(a: &Detection, b: &Detection) -> bool {
     match (a, b) {
@@ -45,16 +69,32 @@ fn detection_types_match(a: &Detection, b: &Detection) -> bool {
     }
 }
 
-/// Apply a single detection patch to a game via WebSocket
-pub async fn apply_patch_to_game(
-    app_handle: &AppHandle,
-    ws_client: &Arc<WebSocketClient>,
+/// Apply a single detection patch to a game via WebSocket. The application in
+/// `AppState` is only updated, and the edit journaled, once G HUB has been sent it.
+pub async fn apply_patch_to_game<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_client: &Arc<WebSocketClient<R>>,
     app_id: &str,
     detection: Detection,
 ) -> Result<(), String> {
-    // Get the current application from state and update it
-    let updated_app = {
-        let state: State<AppState> = app_handle.state();
+    let state: State<AppState> = app_handle.state();
+    let mut updated_app = {
+        let apps = state
+            .applications
+            .lock()
+            .map_err(|e| format!("Failed to acquire lock on applications: {}", e))?;
+
+        apps.iter()
+            .find(|a| a.application_id == app_id)
+            .cloned()
+            .ok_or_else(|| format!("Application with ID '{}' not found", app_id))?
+    }; // Lock is released here
+
+    updated_app.detection.push(detection.clone());
+    send_application(ws_client, &updated_app).await?;
+
+    // Add the detection to the stored app, keeping edits made while sending
+    let (before, after) = {
         let mut apps = state
             .applications
             .lock()
@@ -64,16 +104,26 @@ pub async fn apply_patch_to_game(
             .iter_mut()
             .find(|a| a.application_id == app_id)
             .ok_or_else(|| format!("Application with ID '{}' not found", app_id))?;
+        let before = app.clone();
+        app.detection.push(detection);
+        (before, app.clone())
+    };
 
-        // Add the detection to the app's detection vector
-        app.detection.push(detection.clone());
-
-        // Clone the updated app before releasing the lock
-        app.clone()
-    }; // Lock is released here
+    record_edit(
+        app_handle,
+        EditSource::PatchApplier,
+        "Apply detection patch",
+        Some(before),
+        Some(after),
+    )
+}
 
-    // Send the updated app to G HUB via WebSocket
-    let payload = serde_json::to_value(updated_app)
+/// Send an application to G HUB with a `SET /application`
+pub async fn send_application<R: Runtime>(
+    ws_client: &Arc<WebSocketClient<R>>,
+    app: &GHUBApp,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(app)
         .map_err(|e| format!("Failed to serialize application: {}", e))?;
 
     let message = WebSocketMessage {
@@ -85,15 +135,13 @@ pub async fn apply_patch_to_game(
     ws_client
         .send_message(message)
         .await
-        .map_err(|e| format!("Failed to send WebSocket message: {}", e))?;
-
-    Ok(())
+        .map_err(|e| format!("Failed to send WebSocket message: {}", e))
 }
 
 /// Apply patches for a single game
-pub async fn apply_patches_for_game(
-    app_handle: &AppHandle,
-    ws_client: &Arc<WebSocketClient>,
+pub async fn apply_patches_for_game<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_client: &Arc<WebSocketClient<R>>,
     patches_data: &DetectionPatchesData,
     app_id: &str,
 ) -> Result<Vec<Detection>, String> {
@@ -132,9 +180,9 @@ pub async fn apply_patches_for_game(
 }
 
 /// Apply patches to all games that have missing detections
-pub async fn apply_all_patches(
-    app_handle: &AppHandle,
-    ws_client: &Arc<WebSocketClient>,
+pub async fn apply_all_patches<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_client: &Arc<WebSocketClient<R>>,
 ) -> Result<AppliedPatchesData, String> {
     // Load the bundled patches
     let patches_data = load_detection_patches(app_handle)?;
@@ -197,13 +245,22 @@ pub async fn apply_all_patches(
 }
 
 /// Reapply saved patches on startup
-pub async fn reapply_saved_patches(
-    app_handle: &AppHandle,
-    ws_client: &Arc<WebSocketClient>,
+pub async fn reapply_saved_patches<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_client: &Arc<WebSocketClient<R>>,
 ) -> Result<(), String> {
     // Load saved patches
     let applied_data = load_applied_patches(app_handle)?;
 
+    reapply_patches(app_handle, ws_client, &applied_data).await
+}
+
+/// Reapply a set of previously applied patches
+pub async fn reapply_patches<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    ws_client: &Arc<WebSocketClient<R>>,
+    applied_data: &AppliedPatchesData,
+) -> Result<(), String> {
     if !applied_data.apply_all_defaults && applied_data.per_game_overrides.is_empty() {
         // No patches to reapply
         return Ok(());
@@ -235,3 +292,219 @@ pub async fn reapply_saved_patches(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::models::{SteamApp, WinRegistry};
+    use crate::ghub_game_patches::models::GameDetectionPatches;
+    use crate::websocket::mock_server::{applications_fixture, fixture_apps, mock_app, MockGHubServer};
+    use tauri::test::MockRuntime;
+    use tauri::App;
+
+    const GTA_ID: &str = "14bb5db3-19ba-444d-80d8-0d049a7003ec";
+
+    fn gta_registry_detection() -> Detection {
+        Detection::WinRegistry {
+            win_registry: WinRegistry {
+                executable: "GTA5.exe".to_string(),
+                registry_key: "InstallFolderEpic".to_string(),
+                registry_path: "HKEY_LOCAL_MACHINE/SOFTWARE/WOW6432Node/Rockstar Games/Grand Theft Auto V".to_string(),
+            },
+        }
+    }
+
+    async fn connected_client(
+        app: &App<MockRuntime>,
+        server: &MockGHubServer,
+    ) -> Arc<WebSocketClient<MockRuntime>> {
+        let client = Arc::new(WebSocketClient::new(app.handle().clone()));
+        client
+            .connect(&server.uri())
+            .await
+            .expect("failed to connect to mock server");
+        client
+    }
+
+    fn stored_app(app: &App<MockRuntime>, app_id: &str) -> GHUBApp {
+        let state: State<AppState> = app.state();
+        let apps = state.applications.lock().unwrap();
+        apps.iter().find(|a| a.application_id == app_id).unwrap().clone()
+    }
+
+    #[test]
+    fn missing_detections_compare_by_type_only() {
+        let app = fixture_apps().into_iter().find(|a| a.application_id == GTA_ID).unwrap();
+        let patches = vec![
+            Detection::Steam {
+                steam: SteamApp { app_id: "999".to_string() },
+            },
+            gta_registry_detection(),
+        ];
+
+        let missing = get_missing_detections(&app, &patches);
+
+        assert_eq!(missing.len(), 1);
+        assert!(matches!(missing[0], Detection::WinRegistry { .. }));
+    }
+
+    #[test]
+    fn preview_lists_only_games_with_missing_detections() {
+        let patches_data = DetectionPatchesData {
+            version: 1,
+            patches: vec![
+                GameDetectionPatches {
+                    application_id: GTA_ID.to_string(),
+                    game_name: "Grand Theft Auto V".to_string(),
+                    detections: vec![gta_registry_detection()],
+                },
+                GameDetectionPatches {
+                    application_id: "not-installed".to_string(),
+                    game_name: "Not Installed".to_string(),
+                    detections: vec![gta_registry_detection()],
+                },
+            ],
+        };
+
+        let preview = preview_patches(&fixture_apps(), &patches_data, &[]);
+        assert_eq!(preview.len(), 1);
+        assert_eq!(preview[0].application_id, GTA_ID);
+        assert_eq!(preview[0].missing_detections, vec![gta_registry_detection()]);
+
+        assert!(preview_patches(&fixture_apps(), &patches_data, &["other".to_string()]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn apply_patch_sends_set_application() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+
+        apply_patch_to_game(app.handle(), &client, GTA_ID, gta_registry_detection())
+            .await
+            .unwrap();
+
+        server.wait_for_requests(1).await;
+        let calls = server.set_application_calls().await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0]["applicationId"], GTA_ID);
+        let detections = calls[0]["detection"].as_array().unwrap();
+        assert_eq!(detections.len(), 2);
+        assert_eq!(detections[1]["winRegistry"]["executable"], "GTA5.exe");
+
+        assert_eq!(stored_app(&app, GTA_ID).detection.len(), 2);
+
+        // The patch is journaled so it can be undone before saving
+        let state: State<AppState> = app.state();
+        let journal = state.edit_journal.lock().unwrap().state();
+        assert_eq!(journal.undo_stack.len(), 1);
+        assert_eq!(journal.undo_stack[0].source, EditSource::PatchApplier);
+    }
+
+    #[tokio::test]
+    async fn apply_patch_to_unknown_game_sends_nothing() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+
+        let result = apply_patch_to_game(app.handle(), &client, "missing", gta_registry_detection()).await;
+
+        assert!(result.is_err());
+        assert!(server.requests().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn apply_patch_fails_when_disconnected() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+        client.disconnect().await.unwrap();
+
+        let result = apply_patch_to_game(app.handle(), &client, GTA_ID, gta_registry_detection()).await;
+
+        assert!(result.is_err());
+        // Nothing reached G HUB, so nothing changes locally either
+        assert_eq!(stored_app(&app, GTA_ID).detection.len(), 1);
+        let state: State<AppState> = app.state();
+        assert!(!state.edit_journal.lock().unwrap().state().can_undo);
+    }
+
+    #[tokio::test]
+    async fn apply_patches_for_game_only_sends_missing_types() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+        let patches_data = DetectionPatchesData {
+            version: 1,
+            patches: vec![GameDetectionPatches {
+                application_id: GTA_ID.to_string(),
+                game_name: "Grand Theft Auto V".to_string(),
+                detections: vec![
+                    Detection::Steam {
+                        steam: SteamApp { app_id: "271590".to_string() },
+                    },
+                    gta_registry_detection(),
+                ],
+            }],
+        };
+
+        let applied = apply_patches_for_game(app.handle(), &client, &patches_data, GTA_ID)
+            .await
+            .unwrap();
+        assert_eq!(applied.len(), 1);
+        server.wait_for_requests(1).await;
+
+        // Applying again is a no-op because every detection type is now present
+        let applied = apply_patches_for_game(app.handle(), &client, &patches_data, GTA_ID)
+            .await
+            .unwrap();
+        assert!(applied.is_empty());
+        assert_eq!(server.set_application_calls().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reapply_sends_saved_per_game_overrides() {
+        // Mirrors the startup path: after a G HUB update the saved overrides are pushed again
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+        let applied_data = AppliedPatchesData {
+            apply_all_defaults: false,
+            applied_at: None,
+            per_game_overrides: vec![
+                GamePatchOverride {
+                    application_id: GTA_ID.to_string(),
+                    game_name: "Grand Theft Auto V".to_string(),
+                    applied_detections: vec![gta_registry_detection()],
+                    applied_at: "2025-01-01T00:00:00+00:00".to_string(),
+                },
+                GamePatchOverride {
+                    application_id: "removed-by-update".to_string(),
+                    game_name: "Removed".to_string(),
+                    applied_detections: vec![gta_registry_detection()],
+                    applied_at: "2025-01-01T00:00:00+00:00".to_string(),
+                },
+            ],
+        };
+
+        reapply_patches(app.handle(), &client, &applied_data).await.unwrap();
+
+        server.wait_for_requests(1).await;
+        let calls = server.set_application_calls().await;
+        assert_eq!(calls.len(), 1, "unknown games are skipped without aborting");
+        assert_eq!(calls[0]["applicationId"], GTA_ID);
+    }
+
+    #[tokio::test]
+    async fn reapply_with_nothing_saved_is_a_no_op() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(fixture_apps());
+        let client = connected_client(&app, &server).await;
+
+        reapply_patches(app.handle(), &client, &AppliedPatchesData::default())
+            .await
+            .unwrap();
+
+        assert!(server.requests().await.is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::ghub_game_patches::models::{DetectionPatchesData, GameDetectionPatches};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, Runtime};
 
 /// Load the bundled detection patches from the resources directory
-pub fn load_detection_patches(app_handle: &AppHandle) -> Result<DetectionPatchesData, String> {
+pub fn load_detection_patches<R: Runtime>(app_handle: &AppHandle<R>) -> Result<DetectionPatchesData, String> {
     // Get the resource path
     let resource_path = app_handle
         .path()
@@ -17,6 +17,15 @@ pub struct GameDetectionPatches {
     pub detections: Vec<Detection>,
 }
 
+/// Detections a bundled patch would add to one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchPreview {
+    pub application_id: String,
+    pub game_name: String,
+    pub missing_detections: Vec<Detection>,
+}
+
 /// User's applied patches tracking (saved to applied_patches.json)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1,10 +1,10 @@
 use crate::core::constants::APPLIED_PATCHES_FILENAME;
 use crate::ghub_game_patches::models::AppliedPatchesData;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, Runtime};
 
 /// Get the path to the applied_patches.json file
-pub fn get_applied_patches_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+pub fn get_applied_patches_path<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, String> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
@@ -20,7 +20,7 @@ pub fn get_applied_patches_path(app_handle: &AppHandle) -> Result<PathBuf, Strin
 }
 
 /// Load the applied patches data from disk
-pub fn load_applied_patches(app_handle: &AppHandle) -> Result<AppliedPatchesData, String> {
+pub fn load_applied_patches<R: Runtime>(app_handle: &AppHandle<R>) -> Result<AppliedPatchesData, String> {
     let patches_path = get_applied_patches_path(app_handle)?;
 
     // If the file doesn't exist, return default (empty) data
@@ -40,8 +40,8 @@ pub fn load_applied_patches(app_handle: &AppHandle) -> Result<AppliedPatchesData
 }
 
 /// Save the applied patches data to disk
-pub fn save_applied_patches(
-    app_handle: &AppHandle,
+pub fn save_applied_patches<R: Runtime>(
+    app_handle: &AppHandle<R>,
     data: &AppliedPatchesData,
 ) -> Result<(), String> {
     let patches_path = get_applied_patches_path(app_handle)?;
@@ -6,6 +6,7 @@ mod ghub_game_patches;
 mod game_detection;
 mod tray;
 mod debug;
+pub mod cli;
 
 use std::sync::{Arc, Mutex};
 use tauri::Manager;
@@ -58,32 +59,36 @@ fn initialize_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>
         });
     }
 
-    // Check if G HUB version has changed and reapply patches if needed
+    // Auto-connect to G HUB, then check if its version has changed and reapply patches if needed
     // This is done in a background task since it requires the WebSocket client
     let handle_clone = handle.clone();
     let ws_client_clone = app.state::<Arc<websocket::WebSocketClient>>().inner().clone();
     tauri::async_runtime::spawn(async move {
+        // Connect to G HUB using the configured endpoint if auto-connect is enabled
+        if crate::websocket::config::ConnectionConfig::load(&handle_clone).auto_connect {
+            if let Err(e) = crate::websocket::commands::connect_and_listen(&handle_clone, &ws_client_clone, None).await {
+                eprintln!("Failed to auto-connect to G HUB: {}", e);
+            }
+        }
+
         // Give the app time to fully initialize before checking version
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
 
-        // Check if G HUB version has changed
-        match crate::applications::ghub_version::has_version_changed(&handle_clone) {
-            Ok(true) => {
-                println!("G HUB version has changed - reapplying detection patches");
-
-                if let Err(e) = crate::ghub_game_patches::applier::reapply_saved_patches(
-                    &handle_clone,
-                    &ws_client_clone,
-                ).await {
-                    eprintln!("Warning: Failed to reapply saved detection patches: {}", e);
-                } else {
-                    // Update stored version after successful reapplication
-                    if let Err(e) = crate::applications::ghub_version::update_stored_version(&handle_clone) {
-                        eprintln!("Warning: Failed to update stored G HUB version: {}", e);
-                    }
+        // Re-create custom applications, restore posters and reapply patches if G HUB was updated
+        match crate::applications::ghub_version::restore_after_version_change(&handle_clone, &ws_client_clone, false).await {
+            Ok(restore) if restore.change.is_changed() => {
+                println!(
+                    "G HUB version has changed ({:?}) - re-created {} custom applications, restored {} posters, reapplied patches: {}",
+                    restore.change,
+                    restore.recreated_custom_apps.len(),
+                    restore.restored_posters.len(),
+                    restore.patches_reapplied
+                );
+                for warning in restore.warnings {
+                    eprintln!("Warning: {}", warning);
                 }
             }
-            Ok(false) => {
+            Ok(_) => {
                 println!("G HUB version unchanged - skipping patch reapplication");
             }
             Err(e) => {
@@ -95,6 +100,38 @@ fn initialize_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+fn context() -> tauri::Context<tauri::Wry> {
+    tauri::generate_context!()
+}
+
+fn app_state() -> core::state::AppState {
+    core::state::AppState {
+        applications: Mutex::new(Vec::new()),
+        unparsed_applications: Mutex::new(Vec::new()),
+        edit_journal: Mutex::new(Default::default()),
+        settings_state: Mutex::new(Default::default()),
+    }
+}
+
+/// Build the app without windows, tray or startup tasks, for the CLI
+pub(crate) fn build_headless_app() -> Result<tauri::App, String> {
+    let mut context = context();
+    context.config_mut().app.windows.clear();
+
+    #[allow(unused_mut)]
+    let mut app = tauri::Builder::default()
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .manage(app_state())
+        .build(context)
+        .map_err(|e| format!("Failed to initialize: {}", e))?;
+
+    // Keep the CLI out of the Dock
+    #[cfg(target_os = "macos")]
+    app.set_activation_policy(tauri::ActivationPolicy::Prohibited);
+
+    Ok(app)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -117,20 +154,51 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
-        .manage(core::state::AppState {
-            applications: Mutex::new(Vec::new()),
-            settings_state: Mutex::new(Default::default()),
-        })
+        .manage(app_state())
         .setup(initialize_app)
         .invoke_handler(tauri::generate_handler![
             crate::core::store::store_get_key,
             crate::core::store::store_set_key,
             crate::applications::paths::get_pipeline_path,
             crate::applications::validation::validate_paths,
+            crate::applications::discovery::discover_data_path_candidates,
+            crate::applications::ghub_version::get_ghub_version_info,
             crate::applications::applications_json::get_applications,
             crate::applications::applications_json::update_application,
             crate::applications::applications_json::get_application_by_id,
+            crate::applications::query::query_applications,
+            crate::applications::bulk_edit::bulk_edit_applications,
+            crate::applications::app_validation::check_application,
+            crate::applications::command_library::command_library_edit,
+            crate::applications::command_library::command_library_export,
+            crate::applications::command_library::command_library_import,
+            crate::applications::command_library::command_library_copy,
             crate::applications::applications_json::save_applications_to_disk,
+            crate::applications::applications_json::get_applications_load_report,
+            crate::applications::journal::journal_get_state,
+            crate::applications::journal::journal_undo,
+            crate::applications::journal::journal_redo,
+            crate::applications::journal::get_pending_changes,
+            crate::applications::journal::discard_all_changes,
+            crate::applications::backups::backup_list,
+            crate::applications::backups::backup_create,
+            crate::applications::backups::backup_diff_with_current,
+            crate::applications::backups::backup_restore,
+            crate::applications::custom_apps::custom_app_create,
+            crate::applications::custom_apps::custom_app_list,
+            crate::applications::custom_apps::custom_app_forget,
+            crate::applications::custom_apps::custom_app_recreate_missing,
+            crate::applications::posters::poster_list_files,
+            crate::applications::posters::poster_check_all,
+            crate::applications::posters::poster_replace,
+            crate::applications::posters::poster_forget_override,
+            crate::applications::bundles::bundle_export,
+            crate::applications::bundles::bundle_import,
+            crate::applications::builds::builds_list,
+            crate::applications::builds::build_get_applications,
+            crate::applications::diff::diff_builds,
+            crate::applications::diff::diff_backups,
+            crate::applications::diff::diff_disk_with_memory,
             crate::settings::commands::settings_get_registry,
             crate::settings::commands::settings_get_state,
             crate::settings::commands::settings_set_and_apply,
@@ -147,11 +215,17 @@ pub fn run() {
             crate::game_detection::commands::scan_installed_games,
             crate::game_detection::commands::quick_scan_games,
             crate::game_detection::commands::full_scan_games,
+            crate::game_detection::commands::test_glob_detection,
+            crate::game_detection::commands::evaluate_application_globs,
+            crate::game_detection::evaluator::evaluate_app_detections,
             crate::debug::commands::is_developer_mode,
             crate::debug::commands::open_devtools,
             crate::debug::commands::close_devtools,
             crate::debug::commands::is_devtools_open,
+            crate::debug::commands::get_websocket_log,
+            crate::debug::commands::clear_websocket_log,
+            crate::debug::commands::export_websocket_log,
         ])
-        .run(tauri::generate_context!())
+        .run(context())
         .expect("error while running tauri application");
 }
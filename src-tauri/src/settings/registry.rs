@@ -2,7 +2,16 @@ use once_cell::sync::Lazy;
 use serde_json::json;
 
 use super::models::*;
-use crate::core::constants::{STORE_KEY_AUTOSTART, STORE_KEY_MINIMIZE_TO_TRAY};
+use crate::core::constants::{
+    DEFAULT_BACKUP_RETENTION, DEFAULT_CONNECT_TIMEOUT_MS, DEFAULT_RECONNECT_MAX_ATTEMPTS,
+    DEFAULT_WEBSOCKET_URI, STORE_KEY_AUTOSTART, STORE_KEY_BACKUP_RETENTION,
+    STORE_KEY_GHUB_AUTO_CONNECT, STORE_KEY_GHUB_CONNECT_TIMEOUT_MS,
+    STORE_KEY_GHUB_RECONNECT_MAX_ATTEMPTS, STORE_KEY_GHUB_RECONNECT_POLICY,
+    STORE_KEY_GHUB_WEBSOCKET_URI, STORE_KEY_MINIMIZE_TO_TRAY,
+};
+
+/// `ws://host[:port][/]` with the port restricted to 1-65535
+pub const WEBSOCKET_URI_PATTERN: &str = r"^ws://[A-Za-z0-9.\-]+(:([1-9][0-9]{0,3}|[1-5][0-9]{4}|6[0-4][0-9]{3}|65[0-4][0-9]{2}|655[0-2][0-9]|6553[0-5]))?/?$";
 
 pub static SETTINGS_REGISTRY: Lazy<Vec<Setting>> = Lazy::new(|| {
     vec![
@@ -26,6 +35,106 @@ pub static SETTINGS_REGISTRY: Lazy<Vec<Setting>> = Lazy::new(|| {
             requires_restart: false,
             system_managed: true,
         },
+        Setting {
+            key: STORE_KEY_GHUB_AUTO_CONNECT.into(),
+            label: "Connect to G HUB on Launch".into(),
+            description: Some("Connect to the G HUB agent automatically when the app starts".into()),
+            category: SettingCategory::General,
+            default_value: json!(true),
+            setting_type: SettingType::Toggle,
+            requires_restart: false,
+            system_managed: false,
+        },
+        Setting {
+            key: STORE_KEY_GHUB_WEBSOCKET_URI.into(),
+            label: "G HUB Endpoint".into(),
+            description: Some("WebSocket address of the G HUB agent. Change this only if G HUB runs on a non-default port".into()),
+            category: SettingCategory::Advanced,
+            default_value: json!(DEFAULT_WEBSOCKET_URI),
+            setting_type: SettingType::Text {
+                placeholder: Some(DEFAULT_WEBSOCKET_URI.into()),
+                validation: Some(TextValidation {
+                    pattern: Some(WEBSOCKET_URI_PATTERN.into()),
+                    min_length: Some(1),
+                    max_length: Some(255),
+                }),
+            },
+            requires_restart: false,
+            system_managed: false,
+        },
+        Setting {
+            key: STORE_KEY_GHUB_CONNECT_TIMEOUT_MS.into(),
+            label: "Connection Timeout".into(),
+            description: Some("How long to wait for G HUB to accept a connection".into()),
+            category: SettingCategory::Advanced,
+            default_value: json!(DEFAULT_CONNECT_TIMEOUT_MS),
+            setting_type: SettingType::Number {
+                min: Some(500.0),
+                max: Some(60000.0),
+                step: Some(500.0),
+                unit: Some("ms".into()),
+            },
+            requires_restart: false,
+            system_managed: false,
+        },
+        Setting {
+            key: STORE_KEY_GHUB_RECONNECT_POLICY.into(),
+            label: "Reconnect Policy".into(),
+            description: Some("What to do when the connection to G HUB is lost".into()),
+            category: SettingCategory::Advanced,
+            default_value: json!("backoff"),
+            setting_type: SettingType::Select {
+                options: vec![
+                    SelectOption {
+                        value: "off".into(),
+                        label: "Don't reconnect".into(),
+                        description: None,
+                    },
+                    SelectOption {
+                        value: "fixed".into(),
+                        label: "Fixed interval".into(),
+                        description: Some("Retry every 2 seconds".into()),
+                    },
+                    SelectOption {
+                        value: "backoff".into(),
+                        label: "Exponential backoff".into(),
+                        description: Some("Retry after 1, 2, 4, 8... seconds, up to 30 seconds".into()),
+                    },
+                ],
+            },
+            requires_restart: false,
+            system_managed: false,
+        },
+        Setting {
+            key: STORE_KEY_GHUB_RECONNECT_MAX_ATTEMPTS.into(),
+            label: "Reconnect Attempts".into(),
+            description: Some("Give up reconnecting after this many failed attempts".into()),
+            category: SettingCategory::Advanced,
+            default_value: json!(DEFAULT_RECONNECT_MAX_ATTEMPTS),
+            setting_type: SettingType::Number {
+                min: Some(1.0),
+                max: Some(100.0),
+                step: Some(1.0),
+                unit: None,
+            },
+            requires_restart: false,
+            system_managed: false,
+        },
+        Setting {
+            key: STORE_KEY_BACKUP_RETENTION.into(),
+            label: "Backups to Keep".into(),
+            description: Some("Number of applications.json backups kept before the oldest are deleted".into()),
+            category: SettingCategory::Advanced,
+            default_value: json!(DEFAULT_BACKUP_RETENTION),
+            setting_type: SettingType::Number {
+                min: Some(1.0),
+                max: Some(100.0),
+                step: Some(1.0),
+                unit: None,
+            },
+            requires_restart: false,
+            system_managed: false,
+        },
     ]
 });
 
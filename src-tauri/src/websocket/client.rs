@@ -1,7 +1,6 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::collections::HashMap;
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use tokio::net::TcpStream;
 use tokio_tungstenite::{client_async, WebSocketStream};
 use tokio_tungstenite::tungstenite::{Message, handshake::client::generate_key};
@@ -9,7 +8,13 @@ use tokio_tungstenite::tungstenite::http::Request;
 use futures_util::{SinkExt, StreamExt, stream::{SplitSink, SplitStream}};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Runtime, Wry};
+
+use super::config::{ConnectionConfig, ReconnectPolicy};
+use super::message_log::{MessageDirection, MessageLog};
+use super::transformers::transform_message;
+use crate::applications::live_sync;
+use crate::core::constants::{DEFAULT_WEBSOCKET_PORT, WEBSOCKET_LOG_CAPACITY, WEBSOCKET_RESPONSE_BUFFER};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSocketMessage {
@@ -18,58 +23,111 @@ pub struct WebSocketMessage {
     pub payload: Value,
 }
 
-/// Remove duplicate entries from categoryColors arrays based on tag name
-fn deduplicate_category_colors(message_value: &mut Value) {
-    // Check if this is a response with applications payload
-    if let Some(payload) = message_value.get_mut("payload") {
-        if let Some(applications) = payload.get_mut("applications") {
-            if let Some(apps_array) = applications.as_array_mut() {
-                for app in apps_array.iter_mut() {
-                    if let Some(category_colors) = app.get_mut("categoryColors") {
-                        if let Some(colors_array) = category_colors.as_array_mut() {
-                            let mut seen_tags: HashMap<String, Value> = HashMap::new();
-
-                            // Collect unique entries by tag
-                            for color_entry in colors_array.iter() {
-                                if let Some(tag) = color_entry.get("tag").and_then(|t| t.as_str()) {
-                                    seen_tags
-                                        .entry(tag.to_string())
-                                        .or_insert_with(|| color_entry.clone());
-                                }
-                            }
-
-                            // Replace with deduplicated list
-                            *category_colors = Value::Array(seen_tags.into_values().collect());
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-pub struct WebSocketClient {
+pub struct WebSocketClient<R: Runtime = Wry> {
     pub write_stream: Arc<Mutex<Option<SplitSink<WebSocketStream<TcpStream>, Message>>>>,
     pub read_stream: Arc<Mutex<Option<SplitStream<WebSocketStream<TcpStream>>>>>,
-    pub app_handle: AppHandle,
+    pub app_handle: AppHandle<R>,
     pub is_connected: Arc<AtomicBool>,
+    /// Set by `disconnect` so the reconnect loop knows the close was intentional
+    pub manually_disconnected: Arc<AtomicBool>,
+    /// Set while a `listen_with_reconnect` task is running
+    listening: AtomicBool,
+    /// Recent frames and connection events, queried by the debug commands
+    pub message_log: Arc<std::sync::Mutex<MessageLog>>,
+    /// Every message the listener receives, for `request` to pick its response from
+    incoming: broadcast::Sender<Value>,
+    config: std::sync::Mutex<ConnectionConfig>,
+    last_uri: std::sync::Mutex<Option<String>>,
 }
 
-impl WebSocketClient {
-    pub fn new(app_handle: AppHandle) -> Self {
+impl<R: Runtime> WebSocketClient<R> {
+    pub fn new(app_handle: AppHandle<R>) -> Self {
         Self {
             write_stream: Arc::new(Mutex::new(None)),
             read_stream: Arc::new(Mutex::new(None)),
             app_handle,
             is_connected: Arc::new(AtomicBool::new(false)),
+            manually_disconnected: Arc::new(AtomicBool::new(false)),
+            listening: AtomicBool::new(false),
+            message_log: Arc::new(std::sync::Mutex::new(MessageLog::new(WEBSOCKET_LOG_CAPACITY))),
+            incoming: broadcast::channel(WEBSOCKET_RESPONSE_BUFFER).0,
+            config: std::sync::Mutex::new(ConnectionConfig::default()),
+            last_uri: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Replace the connection settings used for timeouts and reconnects
+    pub fn set_config(&self, config: ConnectionConfig) {
+        if let Ok(mut guard) = self.config.lock() {
+            *guard = config;
+        }
+    }
+
+    pub fn config(&self) -> ConnectionConfig {
+        self.config
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    fn log_frame(&self, direction: MessageDirection, text: &str) {
+        if let Ok(mut log) = self.message_log.lock() {
+            log.record_frame(direction, text);
+        }
+    }
+
+    fn log_event(&self, description: &str) {
+        if let Ok(mut log) = self.message_log.lock() {
+            log.record_event(description);
         }
     }
 
     pub async fn connect(&self, uri: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let timeout = self.config().connect_timeout;
+
+        let result = match tokio::time::timeout(timeout, Self::open_stream(uri)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("Timed out connecting to {} after {} ms", uri, timeout.as_millis()).into()),
+        };
+        let ws_stream = match result {
+            Ok(ws_stream) => ws_stream,
+            Err(e) => {
+                self.log_event(&format!("Failed to connect to {}: {}", uri, e));
+                return Err(e);
+            }
+        };
+
+        // Split the stream into read and write halves
+        let (write, read) = ws_stream.split();
+
+        // Store the split streams
+        {
+            let mut write_guard = self.write_stream.lock().await;
+            *write_guard = Some(write);
+        }
+        {
+            let mut read_guard = self.read_stream.lock().await;
+            *read_guard = Some(read);
+        }
+
+        if let Ok(mut last_uri) = self.last_uri.lock() {
+            *last_uri = Some(uri.to_string());
+        }
+        self.manually_disconnected.store(false, Ordering::SeqCst);
+        self.is_connected.store(true, Ordering::SeqCst);
+        self.log_event(&format!("Connected to {}", uri));
+        let _ = self.app_handle.emit("websocket-connected", "Connected");
+        Ok(())
+    }
+
+    async fn open_stream(uri: &str) -> Result<WebSocketStream<TcpStream>, Box<dyn std::error::Error + Send + Sync>> {
         // Parse the WebSocket URI to extract host and port
         let uri_parsed = uri.parse::<tokio_tungstenite::tungstenite::http::Uri>()?;
+        if uri_parsed.scheme_str() != Some("ws") {
+            return Err(format!("Invalid URI: expected ws:// scheme, got '{}'", uri).into());
+        }
         let host = uri_parsed.host().ok_or("Invalid URI: missing host")?;
-        let port = uri_parsed.port_u16().unwrap_or(9010);
+        let port = uri_parsed.port_u16().unwrap_or(DEFAULT_WEBSOCKET_PORT);
         let addr = format!("{}:{}", host, port);
 
         // Establish TCP connection
@@ -88,23 +146,7 @@ impl WebSocketClient {
 
         // Perform WebSocket handshake
         let (ws_stream, _) = client_async(request, stream).await?;
-        
-        // Split the stream into read and write halves
-        let (write, read) = ws_stream.split();
-
-        // Store the split streams
-        {
-            let mut write_guard = self.write_stream.lock().await;
-            *write_guard = Some(write);
-        }
-        {
-            let mut read_guard = self.read_stream.lock().await;
-            *read_guard = Some(read);
-        }
-
-        self.is_connected.store(true, Ordering::SeqCst);
-        let _ = self.app_handle.emit("websocket-connected", "Connected");
-        Ok(())
+        Ok(ws_stream)
     }
 
     pub async fn send_message(&self, message: WebSocketMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -117,17 +159,44 @@ impl WebSocketClient {
                 "payload": message.payload
             });
             let message_str = json_message.to_string();
-            eprintln!("[WebSocket Client] Sending JSON: {}", message_str);
+            self.log_frame(MessageDirection::Outgoing, &message_str);
             write_stream.send(Message::Text(message_str)).await?;
-            eprintln!("[WebSocket Client] Message sent to server");
         } else {
-            eprintln!("[WebSocket Client] Cannot send message: not connected");
+            self.log_event(&format!("Cannot send {} {}: not connected", message.verb.to_uppercase(), message.path));
             return Err("WebSocket not connected".into());
         }
 
         Ok(())
     }
 
+    /// Send a message and wait for G HUB's response to the same path, up to the
+    /// connect timeout. Needs a listener running, since only it reads the socket.
+    pub async fn request(&self, message: WebSocketMessage) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let mut incoming = self.incoming.subscribe();
+        let path = message.path.clone();
+        self.send_message(message).await?;
+
+        let response = async {
+            loop {
+                match incoming.recv().await {
+                    // Broadcasts carry no result, so they can't be mistaken for the response
+                    Ok(reply) if reply["path"] == path.as_str() && reply.get("result").is_some() => return Ok(reply),
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => return Err("WebSocket client was dropped".to_string()),
+                }
+            }
+        };
+        let timeout = self.config().connect_timeout;
+        let reply = tokio::time::timeout(timeout, response)
+            .await
+            .map_err(|_| format!("Timed out waiting for G HUB to answer {} after {} ms", path, timeout.as_millis()))??;
+
+        match reply["result"]["code"].as_str() {
+            Some("SUCCESS") => Ok(reply),
+            code => Err(format!("G HUB rejected {}: {}", path, code.unwrap_or("no result code")).into()),
+        }
+    }
+
     pub async fn listen_for_messages(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         loop {
             // Acquire the read lock, get the next message, and immediately release the lock
@@ -145,23 +214,23 @@ impl WebSocketClient {
                 Some(Ok(message)) => {
                     match message {
                         Message::Text(text) => {
-                            // Parse, deduplicate, and re-serialize the message
+                            self.log_frame(MessageDirection::Incoming, &text);
+
+                            // Parse, normalize, and re-serialize the message
                             let processed_text = match serde_json::from_str::<Value>(&text) {
                                 Ok(mut message_value) => {
-                                    // Skip OPTIONS messages (don't deduplicate or emit)
+                                    // Skip OPTIONS messages (don't normalize or emit)
                                     if message_value.get("verb").and_then(|v| v.as_str()) == Some("OPTIONS") {
-                                        eprintln!("[WebSocket Client] Skipping OPTIONS message");
                                         continue;
                                     }
 
-                                    // Deduplicate categoryColors for /applications responses
-                                    // Check both request format (with verb) and response format (without verb)
-                                    let is_get_applications = message_value.get("path").and_then(|p| p.as_str()) == Some("/applications");
+                                    // Run the payload transformers registered for this path
+                                    transform_message(&mut message_value);
 
-                                    if is_get_applications {
-                                        eprintln!("[WebSocket Client] Deduplicating categoryColors for /applications response");
-                                        deduplicate_category_colors(&mut message_value);
-                                    }
+                                    // Keep AppState::applications in sync with what G HUB reports
+                                    live_sync::handle_message(&self.app_handle, &message_value);
+                                    // Nobody waiting on a response is fine
+                                    let _ = self.incoming.send(message_value.clone());
 
                                     // Serialize back to string
                                     serde_json::to_string(&message_value).unwrap_or(text)
@@ -176,32 +245,31 @@ impl WebSocketClient {
                             let _ = self.app_handle.emit("websocket-message", processed_text);
                         }
                         Message::Close(_) => {
-                            eprintln!("[WebSocket Client] Connection closed by server");
+                            self.log_event("Connection closed by server");
                             self.is_connected.store(false, Ordering::SeqCst);
                             let _ = self.app_handle.emit("websocket-closed", "Connection closed");
                             break;
                         }
                         Message::Ping(data) => {
-                            eprintln!("[WebSocket Client] Received ping");
                             // Send pong response using write stream
                             let mut write_guard = self.write_stream.lock().await;
                             if let Some(ref mut write_stream) = *write_guard {
                                 let _ = write_stream.send(Message::Pong(data)).await;
                             }
                         }
-                        Message::Pong(_) => {
-                            eprintln!("[WebSocket Client] Received pong");
-                        }
+                        Message::Pong(_) => {}
                         _ => {
-                            eprintln!("[WebSocket Client] Received other message type: {:?}", message);
+                            self.log_event(&format!("Received other message type: {:?}", message));
                         }
                     }
                 }
                 Some(Err(e)) => {
+                    self.log_event(&format!("Connection error: {}", e));
                     self.is_connected.store(false, Ordering::SeqCst);
                     return Err(e.into());
                 },
                 None => {
+                    self.log_event("Connection lost");
                     self.is_connected.store(false, Ordering::SeqCst);
                     break;
                 }
@@ -212,7 +280,76 @@ impl WebSocketClient {
         Ok(())
     }
 
+    /// Listen for messages and, when the connection drops, reconnect according to the
+    /// configured `ReconnectPolicy` until it succeeds or runs out of attempts
+    pub async fn listen_with_reconnect(&self) {
+        loop {
+            if let Err(e) = self.listen_for_messages().await {
+                eprintln!("WebSocket listening error: {}", e);
+            }
+
+            if self.manually_disconnected.load(Ordering::SeqCst) || !self.reconnect().await {
+                self.listening.store(false, Ordering::SeqCst);
+                // A connect made while stopping saw the flag still set and relies on this task
+                if self.is_connected() && self.start_listening() {
+                    continue;
+                }
+                return;
+            }
+        }
+    }
+
+    /// Claim the listener slot. Returns `false` if a listener is already running,
+    /// in which case it will pick up the new connection.
+    pub fn start_listening(&self) -> bool {
+        !self.listening.swap(true, Ordering::SeqCst)
+    }
+
+    /// Try to re-establish the last connection. Returns `true` once reconnected.
+    async fn reconnect(&self) -> bool {
+        let config = self.config();
+        let Some(uri) = self.last_uri.lock().ok().and_then(|guard| guard.clone()) else {
+            return false;
+        };
+
+        for attempt in 1..=config.max_reconnect_attempts {
+            let Some(delay) = config.reconnect_policy.delay(attempt) else {
+                return false;
+            };
+
+            self.log_event(&format!("Reconnect attempt {} in {} ms", attempt, delay.as_millis()));
+            let _ = self.app_handle.emit("websocket-reconnecting", attempt);
+            tokio::time::sleep(delay).await;
+
+            // A manual disconnect or connect during the delay takes precedence
+            if self.manually_disconnected.load(Ordering::SeqCst) {
+                return false;
+            }
+            if self.is_connected() {
+                return true;
+            }
+
+            match self.connect(&uri).await {
+                Ok(()) => {
+                    let _ = self.app_handle.emit("websocket-reconnected", attempt);
+                    return true;
+                }
+                Err(e) => {
+                    self.log_event(&format!("Reconnect attempt {} failed: {}", attempt, e));
+                }
+            }
+        }
+
+        if config.reconnect_policy != ReconnectPolicy::Off {
+            self.log_event("Giving up reconnecting");
+            let _ = self.app_handle.emit("websocket-reconnection-failed", config.max_reconnect_attempts);
+        }
+        false
+    }
+
     pub async fn disconnect(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.manually_disconnected.store(true, Ordering::SeqCst);
+
         // Send close message via write stream
         {
             let mut write_guard = self.write_stream.lock().await;
@@ -229,6 +366,7 @@ impl WebSocketClient {
         }
 
         self.is_connected.store(false, Ordering::SeqCst);
+        self.log_event("Manually disconnected");
         let _ = self.app_handle.emit("websocket-disconnected", "Manually disconnected");
         Ok(())
     }
@@ -236,4 +374,296 @@ impl WebSocketClient {
     pub fn is_connected(&self) -> bool {
         self.is_connected.load(Ordering::SeqCst)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::mock_server::{applications_fixture, mock_app, MockBehavior, MockGHubServer};
+    use std::time::Duration;
+    use tauri::test::MockRuntime;
+    use tauri::{App, Listener};
+    use tokio::sync::mpsc;
+    use tokio::task::JoinHandle;
+
+    type ListenHandle = JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>;
+
+    async fn connect_and_listen(
+        app: &App<MockRuntime>,
+        server: &MockGHubServer,
+    ) -> (Arc<WebSocketClient<MockRuntime>>, ListenHandle) {
+        let client = Arc::new(WebSocketClient::new(app.handle().clone()));
+        client
+            .connect(&server.uri())
+            .await
+            .expect("failed to connect to mock server");
+
+        let listener = client.clone();
+        let handle = tokio::spawn(async move { listener.listen_for_messages().await });
+        (client, handle)
+    }
+
+    /// Forward the payloads of an app event into a channel
+    fn capture_event(app: &App<MockRuntime>, event: &str) -> mpsc::UnboundedReceiver<String> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        app.listen_any(event, move |e| {
+            let _ = tx.send(e.payload().to_string());
+        });
+        rx
+    }
+
+    /// Receive the next `websocket-message` payload and decode the G HUB message inside it
+    async fn next_message(rx: &mut mpsc::UnboundedReceiver<String>) -> Value {
+        let payload = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for websocket-message")
+            .expect("event channel closed");
+        let text: String = serde_json::from_str(&payload).expect("event payload should be a string");
+        serde_json::from_str(&text).expect("message should be JSON")
+    }
+
+    fn get_applications() -> WebSocketMessage {
+        WebSocketMessage {
+            verb: "get".to_string(),
+            path: "/applications".to_string(),
+            payload: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_emits_connected_and_sets_flag() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut connected = capture_event(&app, "websocket-connected");
+
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        assert!(client.is_connected());
+        assert!(connected.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn get_applications_round_trip() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut messages = capture_event(&app, "websocket-message");
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        client.send_message(get_applications()).await.unwrap();
+
+        let requests = server.wait_for_requests(1).await;
+        assert_eq!(requests[0]["verb"], "GET", "verb should be upper-cased on the wire");
+        assert_eq!(requests[0]["path"], "/applications");
+
+        let message = next_message(&mut messages).await;
+        assert_eq!(message["path"], "/applications");
+        let apps = message["payload"]["applications"].as_array().unwrap();
+        assert_eq!(apps.len(), 2);
+        // The fixture repeats the "Combat" tag; the client deduplicates it before emitting
+        assert_eq!(apps[0]["categoryColors"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn applications_response_updates_app_state() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut changed = capture_event(&app, "applications-changed");
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        client.send_message(get_applications()).await.unwrap();
+
+        let payload = tokio::time::timeout(Duration::from_secs(5), changed.recv())
+            .await
+            .expect("timed out waiting for applications-changed")
+            .unwrap();
+        let changes: Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(changes["added"].as_array().unwrap().len(), 2);
+
+        let apps = crate::core::state::get_stored_applications(app.handle()).unwrap();
+        assert_eq!(apps.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn frames_and_connection_events_are_logged() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut messages = capture_event(&app, "websocket-message");
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        client.send_message(get_applications()).await.unwrap();
+        next_message(&mut messages).await;
+
+        let log = client.message_log.lock().unwrap();
+        let directions: Vec<MessageDirection> = log
+            .query(&Default::default())
+            .iter()
+            .map(|e| e.direction)
+            .collect();
+        assert_eq!(
+            directions,
+            vec![MessageDirection::Connection, MessageDirection::Outgoing, MessageDirection::Incoming]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_requests_are_forwarded_to_the_frontend() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        server.set_behavior(MockBehavior::RejectRequests).await;
+        let app = mock_app(Vec::new());
+        let mut messages = capture_event(&app, "websocket-message");
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        client.send_message(get_applications()).await.unwrap();
+
+        let message = next_message(&mut messages).await;
+        assert_eq!(message["result"]["code"], "INVALID_ARG");
+    }
+
+    #[tokio::test]
+    async fn request_returns_the_response_or_the_rejection() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        let reply = client.request(get_applications()).await.unwrap();
+        assert_eq!(reply["payload"]["applications"].as_array().unwrap().len(), 2);
+
+        server.set_behavior(MockBehavior::RejectRequests).await;
+        let error = client.request(get_applications()).await.unwrap_err();
+        assert!(error.to_string().contains("INVALID_ARG"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn send_without_connection_fails() {
+        let app = mock_app(Vec::new());
+        let client = WebSocketClient::new(app.handle().clone());
+
+        assert!(!client.is_connected());
+        assert!(client.send_message(get_applications()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_stops_listener() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let (client, listen) = connect_and_listen(&app, &server).await;
+
+        server.drop_connections();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), listen)
+            .await
+            .expect("listener should stop after the server drops the connection")
+            .unwrap();
+        assert!(result.is_err());
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn scripted_drop_after_request() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        server.set_behavior(MockBehavior::DropAfter(1)).await;
+        let app = mock_app(Vec::new());
+        let (client, listen) = connect_and_listen(&app, &server).await;
+
+        client.send_message(get_applications()).await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), listen)
+            .await
+            .expect("listener should stop after the scripted drop")
+            .unwrap()
+            .ok();
+        assert!(!client.is_connected());
+        assert_eq!(server.requests().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_drop_with_backoff_policy() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut reconnected = capture_event(&app, "websocket-reconnected");
+        let client = Arc::new(WebSocketClient::new(app.handle().clone()));
+        client.connect(&server.uri()).await.unwrap();
+        let listener = client.clone();
+        tokio::spawn(async move { listener.listen_with_reconnect().await });
+
+        server.drop_connections();
+
+        tokio::time::timeout(Duration::from_secs(5), reconnected.recv())
+            .await
+            .expect("client should reconnect")
+            .unwrap();
+        assert!(client.is_connected());
+        client.send_message(get_applications()).await.unwrap();
+        server.wait_for_requests(1).await;
+    }
+
+    #[tokio::test]
+    async fn off_policy_does_not_reconnect() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let mut reconnecting = capture_event(&app, "websocket-reconnecting");
+        let client = Arc::new(WebSocketClient::new(app.handle().clone()));
+        client.set_config(ConnectionConfig {
+            reconnect_policy: ReconnectPolicy::Off,
+            ..ConnectionConfig::default()
+        });
+        client.connect(&server.uri()).await.unwrap();
+        let listener = client.clone();
+        let listen = tokio::spawn(async move { listener.listen_with_reconnect().await });
+
+        server.drop_connections();
+
+        tokio::time::timeout(Duration::from_secs(5), listen)
+            .await
+            .expect("listener should give up immediately")
+            .unwrap();
+        assert!(!client.is_connected());
+        assert!(reconnecting.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn listener_slot_is_released_when_listening_stops() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let client = Arc::new(WebSocketClient::new(app.handle().clone()));
+        client.set_config(ConnectionConfig {
+            reconnect_policy: ReconnectPolicy::Off,
+            ..ConnectionConfig::default()
+        });
+        client.connect(&server.uri()).await.unwrap();
+
+        assert!(client.start_listening());
+        assert!(!client.start_listening());
+
+        let listener = client.clone();
+        let listen = tokio::spawn(async move { listener.listen_with_reconnect().await });
+        server.drop_connections();
+        tokio::time::timeout(Duration::from_secs(5), listen)
+            .await
+            .expect("listener should stop")
+            .unwrap();
+
+        assert!(client.start_listening());
+    }
+
+    #[tokio::test]
+    async fn connect_rejects_non_ws_scheme() {
+        let app = mock_app(Vec::new());
+        let client = WebSocketClient::new(app.handle().clone());
+
+        assert!(client.connect("http://127.0.0.1:9010").await.is_err());
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn disconnect_clears_streams() {
+        let server = MockGHubServer::start(applications_fixture()).await;
+        let app = mock_app(Vec::new());
+        let (client, _listen) = connect_and_listen(&app, &server).await;
+
+        client.disconnect().await.unwrap();
+
+        assert!(!client.is_connected());
+        assert!(client.send_message(get_applications()).await.is_err());
+    }
+}
@@ -1,24 +1,54 @@
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use serde_json::Value;
 
 use super::client::{WebSocketClient, WebSocketMessage};
+use super::config::ConnectionConfig;
 
+/// Connect to G HUB using the configured endpoint, or `uri` if one is given
 #[tauri::command]
 pub async fn ws_connect(
+    app_handle: AppHandle,
     ws_client: State<'_, Arc<WebSocketClient>>,
-    uri: String,
+    uri: Option<String>,
 ) -> Result<(), String> {
+    if ws_client.is_connected() {
+        // Already connected (e.g. auto-connect on launch) - let the caller know
+        let _ = app_handle.emit("websocket-connected", "Connected");
+        return Ok(());
+    }
+
+    connect_and_listen(&app_handle, ws_client.inner(), uri).await
+}
+
+/// Connect with the stored connection settings and start listening in a background task.
+/// Does nothing if already connected, and never starts a second listener.
+pub async fn connect_and_listen(
+    app_handle: &AppHandle,
+    ws_client: &Arc<WebSocketClient>,
+    uri: Option<String>,
+) -> Result<(), String> {
+    if ws_client.is_connected() {
+        return Ok(());
+    }
+
+    let config = ConnectionConfig::load(app_handle);
+    let uri = uri.unwrap_or_else(|| config.uri.clone());
+    ws_client.set_config(config);
+
     ws_client.connect(&uri).await.map_err(|e| e.to_string())?;
-    
+
+    // A listener that is still running reads from the new connection
+    if !ws_client.start_listening() {
+        return Ok(());
+    }
+
     // Start listening for messages in a background task
-    let client_clone = ws_client.inner().clone();
+    let client_clone = ws_client.clone();
     tokio::spawn(async move {
-        if let Err(e) = client_clone.listen_for_messages().await {
-            eprintln!("WebSocket listening error: {}", e);
-        }
+        client_clone.listen_with_reconnect().await;
     });
-    
+
     Ok(())
 }
 
@@ -29,24 +59,13 @@ pub async fn ws_send_message(
     path: String,
     payload: Value,
 ) -> Result<(), String> {
-    eprintln!("[WebSocket] Sending message: verb={}, path={}", verb, path);
-
     let message = WebSocketMessage {
-        verb: verb.clone(),
-        path: path.clone(),
+        verb,
+        path,
         payload,
     };
 
-    match ws_client.send_message(message).await {
-        Ok(_) => {
-            eprintln!("[WebSocket] Message sent successfully: verb={}", verb);
-            Ok(())
-        }
-        Err(e) => {
-            eprintln!("[WebSocket] Failed to send message: verb={}, error={}", verb, e);
-            Err(e.to_string())
-        }
-    }
+    ws_client.send_message(message).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
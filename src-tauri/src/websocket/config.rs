@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::core::constants::{
+    DEFAULT_CONNECT_TIMEOUT_MS, DEFAULT_RECONNECT_MAX_ATTEMPTS, DEFAULT_WEBSOCKET_URI,
+    STORE_KEY_GHUB_AUTO_CONNECT, STORE_KEY_GHUB_CONNECT_TIMEOUT_MS,
+    STORE_KEY_GHUB_RECONNECT_MAX_ATTEMPTS, STORE_KEY_GHUB_RECONNECT_POLICY,
+    STORE_KEY_GHUB_WEBSOCKET_URI,
+};
+use crate::core::store::get_store_key;
+use crate::settings::{registry, validation};
+
+/// What the client does when the connection to G HUB is lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectPolicy {
+    Off,
+    Fixed,
+    Backoff,
+}
+
+impl ReconnectPolicy {
+    const FIXED_DELAY: Duration = Duration::from_secs(2);
+    const MAX_BACKOFF_DELAY: Duration = Duration::from_secs(30);
+
+    pub fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Self::Off),
+            "fixed" => Some(Self::Fixed),
+            "backoff" => Some(Self::Backoff),
+            _ => None,
+        }
+    }
+
+    /// Delay before the given (1-based) reconnect attempt, or `None` if reconnecting is disabled
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        match self {
+            Self::Off => None,
+            Self::Fixed => Some(Self::FIXED_DELAY),
+            Self::Backoff => {
+                let exponent = attempt.saturating_sub(1).min(5);
+                Some(Duration::from_secs(1 << exponent).min(Self::MAX_BACKOFF_DELAY))
+            }
+        }
+    }
+}
+
+/// G HUB connection settings resolved from the settings registry
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    pub uri: String,
+    pub connect_timeout: Duration,
+    pub auto_connect: bool,
+    pub reconnect_policy: ReconnectPolicy,
+    pub max_reconnect_attempts: u32,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            uri: DEFAULT_WEBSOCKET_URI.to_string(),
+            connect_timeout: Duration::from_millis(DEFAULT_CONNECT_TIMEOUT_MS),
+            auto_connect: true,
+            reconnect_policy: ReconnectPolicy::Backoff,
+            max_reconnect_attempts: DEFAULT_RECONNECT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl ConnectionConfig {
+    /// Read the connection settings from the store, falling back to defaults for
+    /// missing values and for values that no longer pass registry validation
+    pub fn load(app_handle: &AppHandle) -> Self {
+        let defaults = Self::default();
+
+        Self {
+            uri: setting_value(app_handle, STORE_KEY_GHUB_WEBSOCKET_URI)
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or(defaults.uri),
+            connect_timeout: setting_value(app_handle, STORE_KEY_GHUB_CONNECT_TIMEOUT_MS)
+                .and_then(|v| v.as_f64())
+                .map(|ms| Duration::from_millis(ms as u64))
+                .unwrap_or(defaults.connect_timeout),
+            auto_connect: setting_value(app_handle, STORE_KEY_GHUB_AUTO_CONNECT)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(defaults.auto_connect),
+            reconnect_policy: setting_value(app_handle, STORE_KEY_GHUB_RECONNECT_POLICY)
+                .and_then(|v| v.as_str().and_then(ReconnectPolicy::from_setting))
+                .unwrap_or(defaults.reconnect_policy),
+            max_reconnect_attempts: setting_value(app_handle, STORE_KEY_GHUB_RECONNECT_MAX_ATTEMPTS)
+                .and_then(|v| v.as_f64())
+                .map(|n| n as u32)
+                .unwrap_or(defaults.max_reconnect_attempts),
+        }
+    }
+}
+
+/// Stored value for a registry key, or `None` if it is missing or fails validation
+fn setting_value(app_handle: &AppHandle, key: &str) -> Option<Value> {
+    let value = get_store_key(app_handle, key)?;
+    let setting = registry::find(key)?;
+
+    match validation::validate_runtime_value(setting, &value) {
+        Ok(()) => Some(value),
+        Err(e) => {
+            eprintln!("Ignoring invalid stored value for '{}': {}", key, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = ReconnectPolicy::Backoff;
+        assert_eq!(policy.delay(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.delay(2), Some(Duration::from_secs(2)));
+        assert_eq!(policy.delay(4), Some(Duration::from_secs(8)));
+        assert_eq!(policy.delay(50), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn off_never_reconnects() {
+        assert_eq!(ReconnectPolicy::Off.delay(1), None);
+    }
+
+    #[test]
+    fn uri_setting_accepts_ws_with_valid_port() {
+        let setting = registry::find(STORE_KEY_GHUB_WEBSOCKET_URI).unwrap();
+        for uri in ["ws://localhost:9010", "ws://127.0.0.1:65535", "ws://ghub.local", "ws://localhost:1/"] {
+            assert!(
+                validation::validate_runtime_value(setting, &Value::from(uri)).is_ok(),
+                "{} should be accepted",
+                uri
+            );
+        }
+    }
+
+    #[test]
+    fn uri_setting_rejects_other_schemes_and_bad_ports() {
+        let setting = registry::find(STORE_KEY_GHUB_WEBSOCKET_URI).unwrap();
+        for uri in ["wss://localhost:9010", "http://localhost:9010", "ws://localhost:0", "ws://localhost:65536", "localhost:9010"] {
+            assert!(
+                validation::validate_runtime_value(setting, &Value::from(uri)).is_err(),
+                "{} should be rejected",
+                uri
+            );
+        }
+    }
+
+    #[test]
+    fn registry_defaults_are_valid() {
+        assert!(validation::validate_registry(registry::all()).is_ok());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::core::constants::WEBSOCKET_LOG_MAX_FRAME_BYTES;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageDirection {
+    /// Frame sent to G HUB
+    Outgoing,
+    /// Frame received from G HUB
+    Incoming,
+    /// Connection lifecycle event (connect, close, reconnect, errors)
+    Connection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageLogEntry {
+    pub id: u64,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    /// Milliseconds since the Unix epoch, used for time range filtering
+    pub timestamp_ms: i64,
+    pub direction: MessageDirection,
+    pub verb: Option<String>,
+    pub path: Option<String>,
+    /// Raw frame text, cut after `WEBSOCKET_LOG_MAX_FRAME_BYTES`, or a
+    /// description for connection events
+    pub text: String,
+}
+
+/// Query parameters for the message log; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MessageLogFilter {
+    pub verb: Option<String>,
+    /// Matches paths starting with this prefix
+    pub path: Option<String>,
+    pub direction: Option<MessageDirection>,
+    pub since_ms: Option<i64>,
+    pub until_ms: Option<i64>,
+    /// Return at most this many of the newest matching entries
+    pub limit: Option<usize>,
+}
+
+impl MessageLogFilter {
+    fn matches(&self, entry: &MessageLogEntry) -> bool {
+        if let Some(verb) = &self.verb {
+            if !entry.verb.as_deref().is_some_and(|v| v.eq_ignore_ascii_case(verb)) {
+                return false;
+            }
+        }
+        if let Some(path) = &self.path {
+            if !entry.path.as_deref().is_some_and(|p| p.starts_with(path.as_str())) {
+                return false;
+            }
+        }
+        if self.direction.is_some_and(|d| d != entry.direction) {
+            return false;
+        }
+        if self.since_ms.is_some_and(|since| entry.timestamp_ms < since) {
+            return false;
+        }
+        if self.until_ms.is_some_and(|until| entry.timestamp_ms > until) {
+            return false;
+        }
+        true
+    }
+}
+
+/// `text` cut to at most `WEBSOCKET_LOG_MAX_FRAME_BYTES`, noting how much was dropped
+fn truncate_frame(text: &str) -> String {
+    if text.len() <= WEBSOCKET_LOG_MAX_FRAME_BYTES {
+        return text.to_string();
+    }
+    let mut end = WEBSOCKET_LOG_MAX_FRAME_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… ({} more bytes)", &text[..end], text.len() - end)
+}
+
+/// Bounded ring buffer of recent WebSocket frames and connection events
+#[derive(Debug)]
+pub struct MessageLog {
+    entries: VecDeque<MessageLogEntry>,
+    capacity: usize,
+    next_id: u64,
+}
+
+impl MessageLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            next_id: 0,
+        }
+    }
+
+    /// Record a frame, pulling `verb` and `path` out of it if it is a G HUB JSON message
+    pub fn record_frame(&mut self, direction: MessageDirection, text: &str) {
+        let parsed = serde_json::from_str::<Value>(text).ok();
+        let field = |key: &str| {
+            parsed
+                .as_ref()
+                .and_then(|v| v.get(key))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+        let (verb, path) = (field("verb"), field("path"));
+
+        self.push(direction, verb, path, truncate_frame(text));
+    }
+
+    /// Record a connection lifecycle event
+    pub fn record_event(&mut self, description: &str) {
+        self.push(MessageDirection::Connection, None, None, description.to_string());
+    }
+
+    fn push(&mut self, direction: MessageDirection, verb: Option<String>, path: Option<String>, text: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        let now = chrono::Utc::now();
+        self.entries.push_back(MessageLogEntry {
+            id: self.next_id,
+            timestamp: now.to_rfc3339(),
+            timestamp_ms: now.timestamp_millis(),
+            direction,
+            verb,
+            path,
+            text,
+        });
+        self.next_id += 1;
+    }
+
+    /// Matching entries, oldest first
+    pub fn query(&self, filter: &MessageLogFilter) -> Vec<MessageLogEntry> {
+        let matching: Vec<&MessageLogEntry> = self.entries.iter().filter(|e| filter.matches(e)).collect();
+        let skip = filter
+            .limit
+            .map(|limit| matching.len().saturating_sub(limit))
+            .unwrap_or(0);
+
+        matching.into_iter().skip(skip).cloned().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_entries_are_evicted_at_capacity() {
+        let mut log = MessageLog::new(2);
+        log.record_event("one");
+        log.record_event("two");
+        log.record_event("three");
+
+        let entries = log.query(&MessageLogFilter::default());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "two");
+        assert_eq!(entries[1].id, 2);
+    }
+
+    #[test]
+    fn frames_are_filtered_by_verb_path_and_direction() {
+        let mut log = MessageLog::new(10);
+        log.record_frame(MessageDirection::Outgoing, r#"{"verb":"GET","path":"/applications"}"#);
+        log.record_frame(MessageDirection::Incoming, r#"{"verb":"GET","path":"/applications","payload":{}}"#);
+        log.record_frame(MessageDirection::Outgoing, r#"{"verb":"SET","path":"/application"}"#);
+        log.record_frame(MessageDirection::Incoming, "not json");
+        log.record_event("connected");
+
+        let gets = log.query(&MessageLogFilter {
+            verb: Some("get".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(gets.len(), 2);
+
+        let outgoing_app = log.query(&MessageLogFilter {
+            path: Some("/application".to_string()),
+            direction: Some(MessageDirection::Outgoing),
+            ..Default::default()
+        });
+        assert_eq!(outgoing_app.len(), 2);
+
+        let events = log.query(&MessageLogFilter {
+            direction: Some(MessageDirection::Connection),
+            ..Default::default()
+        });
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].verb, None);
+    }
+
+    #[test]
+    fn limit_keeps_newest_and_time_range_excludes_outside() {
+        let mut log = MessageLog::new(10);
+        for i in 0..5 {
+            log.record_event(&format!("event {}", i));
+        }
+
+        let newest = log.query(&MessageLogFilter {
+            limit: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(newest.iter().map(|e| e.id).collect::<Vec<_>>(), vec![3, 4]);
+
+        let future = log.query(&MessageLogFilter {
+            since_ms: Some(chrono::Utc::now().timestamp_millis() + 60_000),
+            ..Default::default()
+        });
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn long_frames_are_truncated() {
+        let mut log = MessageLog::new(10);
+        let payload = "é".repeat(WEBSOCKET_LOG_MAX_FRAME_BYTES);
+        let frame = format!(r#"{{"verb":"GET","path":"/applications","payload":"{}"}}"#, payload);
+        log.record_frame(MessageDirection::Incoming, &frame);
+
+        let entry = &log.query(&MessageLogFilter::default())[0];
+        assert_eq!(entry.path.as_deref(), Some("/applications"));
+        assert!(entry.text.len() < WEBSOCKET_LOG_MAX_FRAME_BYTES + 32);
+        assert!(entry.text.ends_with("more bytes)"), "{}", &entry.text[entry.text.len() - 40..]);
+    }
+
+    #[test]
+    fn clear_empties_the_log() {
+        let mut log = MessageLog::new(10);
+        log.record_event("connected");
+        log.clear();
+        assert!(log.query(&Default::default()).is_empty());
+    }
+}
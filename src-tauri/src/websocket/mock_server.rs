@@ -0,0 +1,311 @@
+//! In-process stand-in for the G HUB WebSocket agent used by the integration tests.
+//!
+//! The server speaks the `json` subprotocol, answers `GET /applications` from a fixture,
+//! records every request it receives and can be scripted to reject requests or drop
+//! connections so that error paths in the client and patch applier can be exercised
+//! without a live G HUB on port 9010.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{watch, Mutex};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::{HeaderValue, StatusCode};
+use tokio_tungstenite::tungstenite::Message;
+use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+
+use crate::applications::models::{ApplicationsData, GHUBApp};
+use crate::core::state::AppState;
+
+/// How the mock server responds to incoming requests
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockBehavior {
+    /// Answer every request like G HUB would
+    Normal,
+    /// Answer every request with a non-success result code
+    RejectRequests,
+    /// Drop the TCP connection without a close frame after this many requests
+    DropAfter(usize),
+}
+
+#[derive(Debug)]
+struct MockState {
+    applications: Value,
+    requests: Vec<Value>,
+    behavior: MockBehavior,
+}
+
+pub struct MockGHubServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    drop_signal: watch::Sender<u64>,
+}
+
+impl MockGHubServer {
+    /// Start a server on an ephemeral localhost port serving the given `applications` payload
+    pub async fn start(applications: Value) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock G HUB server");
+        let addr = listener.local_addr().expect("mock server has no local address");
+
+        let state = Arc::new(Mutex::new(MockState {
+            applications,
+            requests: Vec::new(),
+            behavior: MockBehavior::Normal,
+        }));
+        let (drop_signal, _) = watch::channel(0u64);
+
+        let accept_state = state.clone();
+        let accept_signal = drop_signal.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                // Subscribe before the handshake so a drop requested right after connecting is not missed
+                tokio::spawn(handle_connection(
+                    stream,
+                    accept_state.clone(),
+                    accept_signal.subscribe(),
+                ));
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            drop_signal,
+        }
+    }
+
+    /// WebSocket URI clients should connect to
+    pub fn uri(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    pub async fn set_behavior(&self, behavior: MockBehavior) {
+        self.state.lock().await.behavior = behavior;
+    }
+
+    /// Abruptly drop every open connection
+    pub fn drop_connections(&self) {
+        self.drop_signal.send_modify(|generation| *generation += 1);
+    }
+
+    /// Every request received so far, in arrival order
+    pub async fn requests(&self) -> Vec<Value> {
+        self.state.lock().await.requests.clone()
+    }
+
+    /// Payloads of every `SET /application` request received so far
+    pub async fn set_application_calls(&self) -> Vec<Value> {
+        self.requests()
+            .await
+            .into_iter()
+            .filter(|r| r["verb"] == "SET" && r["path"] == "/application")
+            .map(|r| r["payload"].clone())
+            .collect()
+    }
+
+    /// Wait until at least `count` requests have been received, or panic after a timeout
+    pub async fn wait_for_requests(&self, count: usize) -> Vec<Value> {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            let requests = self.requests().await;
+            if requests.len() >= count {
+                return requests;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                panic!(
+                    "timed out waiting for {} requests, received {}",
+                    count,
+                    requests.len()
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+/// The default application fixture served by the mock server
+pub fn applications_fixture() -> Value {
+    json!({
+        "applications": [
+            {
+                "applicationId": "14bb5db3-19ba-444d-80d8-0d049a7003ec",
+                "name": "Grand Theft Auto V",
+                "categoryColors": [
+                    { "hex": "#ff0000", "tag": "Combat" },
+                    { "hex": "#00ff00", "tag": "Movement" },
+                    { "hex": "#ff0000", "tag": "Combat" }
+                ],
+                "commands": [],
+                "detection": [
+                    { "steam": { "appId": "271590" } }
+                ],
+                "posterTitlePosition": "bottom",
+                "posterUrl": "file://images/gta5.jpg",
+                "version": 1
+            },
+            {
+                "applicationId": "a2d8a1b2-5c3e-4c47-9e0f-1f2e3d4c5b6a",
+                "name": "Desktop",
+                "categoryColors": [],
+                "commands": [],
+                "detection": [],
+                "posterTitlePosition": "",
+                "posterUrl": "",
+                "version": 1
+            }
+        ]
+    })
+}
+
+/// The default fixture parsed into the models held in `AppState`
+pub fn fixture_apps() -> Vec<GHUBApp> {
+    serde_json::from_value::<ApplicationsData>(applications_fixture())
+        .expect("fixture should parse into ApplicationsData")
+        .applications
+}
+
+/// Build a mock Tauri app whose `AppState` is preloaded with the given applications
+pub fn mock_app(applications: Vec<GHUBApp>) -> tauri::App<MockRuntime> {
+    mock_builder()
+        .manage(AppState {
+            applications: std::sync::Mutex::new(applications),
+            unparsed_applications: std::sync::Mutex::new(Vec::new()),
+            edit_journal: std::sync::Mutex::new(Default::default()),
+            settings_state: std::sync::Mutex::new(Default::default()),
+        })
+        .build(mock_context(noop_assets()))
+        .expect("failed to build mock app")
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    state: Arc<Mutex<MockState>>,
+    mut drop_signal: watch::Receiver<u64>,
+) {
+    let callback = |request: &Request, mut response: Response| -> Result<Response, ErrorResponse> {
+        let offers_json = request
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .and_then(|v| v.to_str().ok())
+            .map(|protocols| protocols.split(',').any(|p| p.trim() == "json"))
+            .unwrap_or(false);
+
+        if !offers_json {
+            let mut error = ErrorResponse::new(Some("json subprotocol required".to_string()));
+            *error.status_mut() = StatusCode::BAD_REQUEST;
+            return Err(error);
+        }
+
+        response
+            .headers_mut()
+            .insert("Sec-WebSocket-Protocol", HeaderValue::from_static("json"));
+        Ok(response)
+    };
+
+    let Ok(ws_stream) = tokio_tungstenite::accept_hdr_async(stream, callback).await else {
+        return;
+    };
+    let (mut write, mut read) = ws_stream.split();
+
+    loop {
+        let message = tokio::select! {
+            _ = drop_signal.changed() => return,
+            message = read.next() => message,
+        };
+
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Ping(data))) => {
+                let _ = write.send(Message::Pong(data)).await;
+                continue;
+            }
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+            Some(Ok(_)) => continue,
+        };
+
+        let Ok(request) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+
+        let response = {
+            let mut state = state.lock().await;
+            state.requests.push(request.clone());
+
+            if let MockBehavior::DropAfter(limit) = state.behavior {
+                if state.requests.len() >= limit {
+                    // Dropping both halves closes the socket without a close frame
+                    return;
+                }
+            }
+
+            respond(&mut state, &request)
+        };
+
+        if write.send(Message::Text(response.to_string())).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Build the response G HUB would send for a request
+fn respond(state: &mut MockState, request: &Value) -> Value {
+    let verb = request["verb"].as_str().unwrap_or_default();
+    let path = request["path"].as_str().unwrap_or_default();
+
+    if state.behavior == MockBehavior::RejectRequests {
+        return json!({
+            "msgId": "",
+            "verb": verb,
+            "path": path,
+            "origin": "backend",
+            "result": { "code": "INVALID_ARG", "what": "rejected by mock server" },
+            "payload": {}
+        });
+    }
+
+    let payload = match (verb, path) {
+        ("GET", "/applications") => state.applications.clone(),
+        ("SET", "/application") => {
+            let updated = request["payload"].clone();
+            if let Some(apps) = state
+                .applications
+                .get_mut("applications")
+                .and_then(|a| a.as_array_mut())
+            {
+                match apps
+                    .iter_mut()
+                    .find(|a| a["applicationId"] == updated["applicationId"])
+                {
+                    Some(existing) => *existing = updated.clone(),
+                    None => apps.push(updated.clone()),
+                }
+            }
+            updated
+        }
+        _ => {
+            return json!({
+                "msgId": "",
+                "verb": verb,
+                "path": path,
+                "origin": "backend",
+                "result": { "code": "NO_SUCH_PATH", "what": "" },
+                "payload": {}
+            });
+        }
+    };
+
+    json!({
+        "msgId": "",
+        "verb": verb,
+        "path": path,
+        "origin": "backend",
+        "result": { "code": "SUCCESS", "what": "" },
+        "payload": payload
+    })
+}
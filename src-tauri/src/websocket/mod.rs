@@ -1,4 +1,9 @@
 pub mod client;
 pub mod commands;
+pub mod config;
+pub mod message_log;
+pub mod transformers;
+#[cfg(test)]
+pub mod mock_server;
 
-pub use client::WebSocketClient;
\ No newline at end of file
+pub use client::WebSocketClient;
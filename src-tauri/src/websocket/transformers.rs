@@ -0,0 +1,323 @@
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// A normalisation step applied to incoming G HUB messages before they are
+/// emitted to the frontend or handed to Rust consumers.
+pub trait PayloadTransformer: Send + Sync {
+    fn apply(&self, message: &mut Value);
+}
+
+/// Transformers to run for each message path, in order
+pub static TRANSFORMERS: Lazy<HashMap<&'static str, Vec<Box<dyn PayloadTransformer>>>> =
+    Lazy::new(|| {
+        let mut map: HashMap<&'static str, Vec<Box<dyn PayloadTransformer>>> = HashMap::new();
+        map.insert("/applications", application_pipeline());
+        map.insert("/application", application_pipeline());
+        map
+    });
+
+fn application_pipeline() -> Vec<Box<dyn PayloadTransformer>> {
+    vec![
+        Box::new(NormalizeApplicationKeys),
+        Box::new(CoerceApplicationSchema),
+        Box::new(DeduplicateCategoryColors),
+    ]
+}
+
+/// Run every transformer registered for the message's path
+pub fn transform_message(message: &mut Value) {
+    let Some(path) = message.get("path").and_then(|p| p.as_str()) else {
+        return;
+    };
+
+    if let Some(transformers) = TRANSFORMERS.get(path) {
+        for transformer in transformers {
+            transformer.apply(message);
+        }
+    }
+}
+
+/// Application objects carried by a message: the `applications` array of a
+/// `/applications` payload, or the payload itself for `/application`
+fn application_payloads_mut(message: &mut Value) -> Vec<&mut Value> {
+    let path = message.get("path").and_then(|p| p.as_str()).map(String::from);
+    let Some(payload) = message.get_mut("payload") else {
+        return Vec::new();
+    };
+
+    match path.as_deref() {
+        Some("/applications") => payload
+            .get_mut("applications")
+            .and_then(|apps| apps.as_array_mut())
+            .map(|apps| apps.iter_mut().filter(|app| app.is_object()).collect())
+            .unwrap_or_default(),
+        Some("/application") if payload.is_object() => vec![payload],
+        _ => Vec::new(),
+    }
+}
+
+/// Remove duplicate `categoryColors` entries by tag, keeping the first
+/// occurrence and the original order
+pub struct DeduplicateCategoryColors;
+
+impl PayloadTransformer for DeduplicateCategoryColors {
+    fn apply(&self, message: &mut Value) {
+        for app in application_payloads_mut(message) {
+            if let Some(colors) = app.get_mut("categoryColors").and_then(|c| c.as_array_mut()) {
+                let mut seen_tags = HashSet::new();
+                colors.retain(|entry| match entry.get("tag").and_then(|t| t.as_str()) {
+                    Some(tag) => seen_tags.insert(tag.to_string()),
+                    None => true,
+                });
+            }
+        }
+    }
+}
+
+/// Rename snake_case keys G HUB occasionally sends to the camelCase names used
+/// everywhere else, and trim stray whitespace from category tags and colors
+pub struct NormalizeApplicationKeys;
+
+impl NormalizeApplicationKeys {
+    const KEY_ALIASES: [(&'static str, &'static str); 4] = [
+        ("application_id", "applicationId"),
+        ("category_colors", "categoryColors"),
+        ("poster_title_position", "posterTitlePosition"),
+        ("poster_url", "posterUrl"),
+    ];
+}
+
+impl PayloadTransformer for NormalizeApplicationKeys {
+    fn apply(&self, message: &mut Value) {
+        for app in application_payloads_mut(message) {
+            let Some(fields) = app.as_object_mut() else {
+                continue;
+            };
+
+            for (alias, canonical) in Self::KEY_ALIASES {
+                if let Some(value) = fields.remove(alias) {
+                    // An explicit camelCase value wins over the alias
+                    fields.entry(canonical).or_insert(value);
+                }
+            }
+
+            if let Some(colors) = fields.get_mut("categoryColors").and_then(|c| c.as_array_mut()) {
+                for entry in colors.iter_mut().filter_map(|e| e.as_object_mut()) {
+                    trim_string_field(entry, "tag");
+                    trim_string_field(entry, "hex");
+                }
+            }
+        }
+    }
+}
+
+fn trim_string_field(fields: &mut Map<String, Value>, key: &str) {
+    if let Some(Value::String(s)) = fields.get_mut(key) {
+        let trimmed = s.trim();
+        if trimmed.len() != s.len() {
+            *s = trimmed.to_string();
+        }
+    }
+}
+
+/// Coerce loosely typed values into the shapes `GHUBApp` expects so the
+/// payload deserializes instead of being dropped: `null` lists become empty,
+/// numeric ids become strings, a stringified `version` becomes a number and a
+/// single keystroke string becomes a one-element list. Unknown fields are left untouched.
+pub struct CoerceApplicationSchema;
+
+impl CoerceApplicationSchema {
+    const LIST_FIELDS: [&'static str; 3] = ["categoryColors", "commands", "detection"];
+    const STRING_FIELDS: [&'static str; 4] = ["applicationId", "name", "posterTitlePosition", "posterUrl"];
+    const DETECTION_ID_FIELDS: [(&'static str, &'static str); 3] = [
+        ("steam", "appId"),
+        ("uplay", "appId"),
+        ("gogGalaxy", "productId"),
+    ];
+}
+
+impl PayloadTransformer for CoerceApplicationSchema {
+    fn apply(&self, message: &mut Value) {
+        for app in application_payloads_mut(message) {
+            let Some(fields) = app.as_object_mut() else {
+                continue;
+            };
+
+            for key in Self::LIST_FIELDS {
+                if matches!(fields.get(key), Some(Value::Null)) {
+                    fields.insert(key.to_string(), Value::Array(Vec::new()));
+                }
+            }
+
+            for key in Self::STRING_FIELDS {
+                if let Some(value) = fields.get_mut(key) {
+                    number_to_string(value);
+                }
+            }
+
+            if let Some(version) = fields.get_mut("version") {
+                if let Some(parsed) = version.as_str().and_then(|s| s.trim().parse::<u32>().ok()) {
+                    *version = Value::from(parsed);
+                }
+            }
+
+            if let Some(commands) = fields.get_mut("commands").and_then(|c| c.as_array_mut()) {
+                for command in commands.iter_mut() {
+                    if let Some(keystroke) = command.get_mut("keystroke") {
+                        if keystroke.is_string() {
+                            *keystroke = Value::Array(vec![keystroke.take()]);
+                        }
+                    }
+                }
+            }
+
+            if let Some(detections) = fields.get_mut("detection").and_then(|d| d.as_array_mut()) {
+                for detection in detections.iter_mut() {
+                    for (platform, id_field) in Self::DETECTION_ID_FIELDS {
+                        if let Some(id) = detection.get_mut(platform).and_then(|p| p.get_mut(id_field)) {
+                            number_to_string(id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn number_to_string(value: &mut Value) {
+    if let Value::Number(n) = value {
+        *value = Value::String(n.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::applications::models::GHUBApp;
+    use serde_json::json;
+
+    fn applications_message(apps: Value) -> Value {
+        json!({ "verb": "GET", "path": "/applications", "payload": { "applications": apps } })
+    }
+
+    #[test]
+    fn dedup_keeps_first_occurrence_in_order() {
+        let mut message = applications_message(json!([{
+            "applicationId": "a",
+            "categoryColors": [
+                { "hex": "#3", "tag": "Zeta" },
+                { "hex": "#1", "tag": "Alpha" },
+                { "hex": "#2", "tag": "Zeta" },
+                { "hex": "#4", "tag": "Mid" }
+            ]
+        }]));
+
+        DeduplicateCategoryColors.apply(&mut message);
+
+        let colors = &message["payload"]["applications"][0]["categoryColors"];
+        assert_eq!(
+            colors,
+            &json!([
+                { "hex": "#3", "tag": "Zeta" },
+                { "hex": "#1", "tag": "Alpha" },
+                { "hex": "#4", "tag": "Mid" }
+            ])
+        );
+    }
+
+    #[test]
+    fn dedup_applies_to_single_application_payload() {
+        let mut message = json!({
+            "path": "/application",
+            "payload": {
+                "applicationId": "a",
+                "categoryColors": [{ "hex": "#1", "tag": "A" }, { "hex": "#2", "tag": "A" }]
+            }
+        });
+
+        DeduplicateCategoryColors.apply(&mut message);
+
+        assert_eq!(message["payload"]["categoryColors"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn normalize_renames_snake_case_keys_without_overwriting() {
+        let mut message = applications_message(json!([{
+            "applicationId": "a",
+            "poster_url": "file://a.jpg",
+            "poster_title_position": "top",
+            "posterTitlePosition": "bottom",
+            "categoryColors": [{ "hex": " #ff0000 ", "tag": "Combat " }]
+        }]));
+
+        NormalizeApplicationKeys.apply(&mut message);
+
+        let app = &message["payload"]["applications"][0];
+        assert_eq!(app["posterUrl"], "file://a.jpg");
+        assert_eq!(app["posterTitlePosition"], "bottom");
+        assert!(app.get("poster_url").is_none());
+        assert!(app.get("poster_title_position").is_none());
+        assert_eq!(app["categoryColors"][0], json!({ "hex": "#ff0000", "tag": "Combat" }));
+    }
+
+    #[test]
+    fn coerce_fixes_types_and_keeps_unknown_fields() {
+        let mut message = applications_message(json!([{
+            "applicationId": "a",
+            "name": "Game",
+            "databaseId": "db-1",
+            "version": "7",
+            "categoryColors": null,
+            "commands": [{ "category": "c", "keystroke": "SPACE", "name": "Jump" }],
+            "detection": [
+                { "steam": { "appId": 271590 } },
+                { "gogGalaxy": { "productId": 1207658924 } }
+            ]
+        }]));
+
+        CoerceApplicationSchema.apply(&mut message);
+
+        let app = &message["payload"]["applications"][0];
+        assert_eq!(app["version"], 7);
+        assert_eq!(app["categoryColors"], json!([]));
+        assert_eq!(app["commands"][0]["keystroke"], json!(["SPACE"]));
+        assert_eq!(app["detection"][0]["steam"]["appId"], "271590");
+        assert_eq!(app["detection"][1]["gogGalaxy"]["productId"], "1207658924");
+        assert_eq!(app["databaseId"], "db-1");
+
+        let parsed: GHUBApp = serde_json::from_value(app.clone()).expect("coerced app should parse");
+        assert_eq!(parsed.version, 7);
+    }
+
+    #[test]
+    fn pipeline_only_runs_for_registered_paths() {
+        let original = json!({
+            "path": "/devices",
+            "payload": { "categoryColors": [{ "tag": "A" }, { "tag": "A" }] }
+        });
+        let mut message = original.clone();
+
+        transform_message(&mut message);
+
+        assert_eq!(message, original);
+    }
+
+    #[test]
+    fn pipeline_runs_all_application_transformers() {
+        let mut message = applications_message(json!([{
+            "application_id": "a",
+            "name": "Game",
+            "version": "2",
+            "categoryColors": [{ "hex": "#1", "tag": "A" }, { "hex": "#2", "tag": "A " }]
+        }]));
+
+        transform_message(&mut message);
+
+        let app = &message["payload"]["applications"][0];
+        assert_eq!(app["applicationId"], "a");
+        assert_eq!(app["version"], 2);
+        // Tags are trimmed before deduplication, so "A " is recognised as a duplicate
+        assert_eq!(app["categoryColors"], json!([{ "hex": "#1", "tag": "A" }]));
+    }
+}
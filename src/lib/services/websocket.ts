@@ -2,9 +2,8 @@ import { invoke } from '@tauri-apps/api/core';
 import type { ApplicationPayload } from '../types';
 
 class WebSocketService {
-    private readonly DEFAULT_URI = 'ws://localhost:9010';
-
-    async connect(uri: string): Promise<void> {
+    // Omitting the URI connects to the endpoint configured in settings
+    async connect(uri?: string): Promise<void> {
         try {
             await invoke('ws_connect', { uri });
         } catch (error) {
@@ -47,17 +46,6 @@ class WebSocketService {
         }
     }
 
-    async autoConnect(): Promise<void> {
-        try {
-            console.log('Attempting to connect to WebSocket at:', this.DEFAULT_URI);
-            await this.connect(this.DEFAULT_URI);
-            console.log('WebSocket connected successfully');
-        } catch (error) {
-            console.error('Auto-connect failed:', error);
-            console.error('Make sure a WebSocket server is running at:', this.DEFAULT_URI);
-        }
-    }
-
     // Action helpers - fire-and-forget commands that trigger responses via WebSocket events
     async getApplications(): Promise<void> {
         return this.send('GET', '/applications');
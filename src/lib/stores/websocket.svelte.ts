@@ -1,4 +1,5 @@
 import { writable, derived } from 'svelte/store';
+import { invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type { ApplicationPayload, WebSocketMessage, GHUBApp } from '../types';
 import { applicationPayloadToGHUBApp } from '../types';
@@ -113,6 +114,11 @@ export async function initializeWebSocketStores() {
     });
     unlistenFunctions.push(unlistenMessage);
 
+    // The backend may have connected on launch before these listeners existed
+    if (await invoke<boolean>('ws_is_connected')) {
+      wsConnected.set(true);
+    }
+
     console.log('[WebSocket Stores] Initialization complete');
   } catch (error) {
     console.error('[WebSocket Stores] Failed to initialize event listeners:', error);
@@ -69,6 +69,83 @@ export interface EaApp {
   gameId: string;
 }
 
+// Entries of applications.json that could not be parsed; they are kept and
+// written back unchanged on save
+export interface SkippedApplication {
+  index: number;
+  reason: string;
+  applicationId: string | null;
+  name: string | null;
+}
+
+// Detections of one key g-hauler doesn't recognise; they are written back unchanged
+export interface UnknownDetectionSummary {
+  key: string | null;
+  malformed: boolean;
+  count: number;
+  applicationIds: string[];
+}
+
+export interface ApplicationsLoadReport {
+  loaded: number;
+  skipped: SkippedApplication[];
+  unknownDetections: UnknownDetectionSummary[];
+}
+
+// Problem with one field of an application, e.g. path "detection[1].steam.appId"
+export interface ValidationIssue {
+  path: string;
+  message: string;
+  severity: 'error' | 'warning';
+}
+
+export type PosterStatus = 'ok' | 'none' | 'remote' | 'missing' | 'broken';
+
+export interface PosterCheck {
+  applicationId: string;
+  name: string;
+  posterUrl: string;
+  path: string | null;
+  status: PosterStatus;
+}
+
+export type CheckedPath =
+  | 'dataDir'
+  | 'currentJson'
+  | 'versionJson'
+  | 'buildDir'
+  | 'applicationsJson'
+  | 'imagesDir';
+
+export type ParseStatus = { status: 'ok' } | { status: 'failed'; error: string };
+
+export interface PathCheck {
+  name: CheckedPath;
+  label: string;
+  path: string | null;
+  exists: boolean;
+  readable: boolean;
+  writable: boolean | null;
+  parseStatus: ParseStatus | null;
+  ok: boolean;
+  hint: string | null;
+}
+
+// Result of validate_paths for the configured G HUB data directory
+export interface PathDiagnostics {
+  dataPath: string | null;
+  buildId: string | null;
+  checks: PathCheck[];
+  isValid: boolean;
+  canSave: boolean;
+}
+
+export interface PosterFile {
+  path: string;
+  size: number;
+  usedBy: string[];
+}
+
 // WebSocket payload interfaces
 
 export interface ApplicationPayload {